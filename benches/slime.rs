@@ -7,7 +7,6 @@ use criterion::Criterion;
 use mc_utils::alg;
 use mc_utils::rand::is_slimechunk;
 use mc_utils::rand::is_slimechunk_inline;
-use mc_utils::rand::slime::is_slimechunk_emma;
 use mc_utils::util::ChunkPoint;
 use rand::distributions::Uniform;
 use rand::Rng;
@@ -82,14 +81,6 @@ fn criterion_benchmark(c: &mut Criterion) {
         )
     });
 
-    slime_group.bench_function("is_slimechunk_emma", move |b| {
-        b.iter_batched(
-            || is_slimechunk_data,
-            |(seed, x, z)| is_slimechunk_emma(seed, x, z),
-            BatchSize::LargeInput,
-        )
-    });
-
     slime_group.bench_function("slime_search_rect", move |b| {
         b.iter_batched(
             || slime_search_data,
@@ -0,0 +1,454 @@
+use std::ops::Range;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+use ndarray::Array2;
+
+use crate::biome::overworld::{self, ClimateSampler, ContinentalnessSampler};
+use crate::biome::spawn::find_spawn;
+use crate::biome::{quantize, quart, Biome, BiomeMap, BiomeSource, Parameter, ParameterList};
+use crate::rand::FlowerForestFlower;
+use crate::util::{BlockPoint, BoundingBox, Point3D, Version};
+
+use super::flowers::{search_all_flowers, FlowerSearchResult};
+
+/// Searches for the square area of at least `size` by `size` blocks covered by `biomes` whose
+/// center is closest to `center`. Biomes are sampled every `scale` blocks on the `y` level of
+/// `center`, a larger `scale` is faster but may miss narrow gaps in the area.
+///
+/// Only areas within `max_radius` blocks of `center` along both axes are considered. The search
+/// starts close to `center` and widens until an area is found.
+pub fn nearest_biome_area<S: BiomeSource>(
+    source: &S,
+    center: BlockPoint,
+    biomes: &[Biome],
+    size: u32,
+    max_radius: u32,
+    scale: u32,
+) -> Option<BoundingBox> {
+    assert!(size > 0);
+    assert!(scale > 0);
+
+    let mut radius = (2 * size).min(max_radius);
+
+    loop {
+        let r = radius as i32;
+        let map = BiomeMap::new(
+            source,
+            Point3D {
+                x: center.x - r,
+                z: center.z - r,
+                ..center
+            },
+            Point3D {
+                x: center.x + r,
+                z: center.z + r,
+                ..center
+            },
+            scale,
+        );
+
+        let found = nearest_square(&map, center, biomes, size);
+
+        // an area further than `radius - size` might lose to one reaching out of the map
+        let certain = found.is_some_and(|(dist, _)| dist <= (radius as i64 - size as i64).pow(2));
+
+        if certain || radius >= max_radius {
+            return found.map(|(_, area)| area);
+        }
+
+        radius = (2 * radius).min(max_radius);
+    }
+}
+
+/// Searches for the flower forest closest to `center` of at least `size` by `size` blocks, then
+/// for the positions inside it from which each flower in `flowers` is within `max_flower_radius`
+/// blocks, see `search_all_flowers`.
+///
+/// Only results whose flowers are all in a flower forest are kept, sorted by ascending radius.
+pub fn search_flower_forest<S: BiomeSource>(
+    source: &S,
+    center: BlockPoint,
+    size: u32,
+    max_radius: u32,
+    max_flower_radius: u32,
+    flowers: &[FlowerForestFlower],
+) -> Option<(BoundingBox, Vec<FlowerSearchResult>)> {
+    let area = nearest_biome_area(source, center, &[Biome::FlowerForest], size, max_radius, 4)?;
+
+    let results = search_all_flowers(area.min, area.max, max_flower_radius, flowers)
+        .into_iter()
+        .filter(|res| {
+            res.flowers
+                .iter()
+                .all(|(_, p)| area.contains(p) || source.biome_at(*p) == Biome::FlowerForest)
+        })
+        .collect();
+
+    Some((area, results))
+}
+
+// the number of consecutive seeds a thread checks at once
+const SEED_BATCH: u64 = 256;
+
+/// A search for overworld seeds with one of a set of biomes within a radius of a point, from 1.18
+/// on. Seeds are first filtered on continentalness alone, which is way cheaper to sample than the
+/// whole climate, before the biomes are computed.
+#[derive(Clone, Debug)]
+pub struct BiomeSeedSearch {
+    biomes: Vec<Biome>,
+    center: BlockPoint,
+    around_spawn: bool,
+    radius: u32,
+    scale: u32,
+    threads: usize,
+    parameters: ParameterList<Biome>,
+
+    /// The continentalness of every wanted biome, `None` if the overworld never generates them.
+    continentalness: Option<Parameter>,
+
+    /// The continentalness of every overworld biome.
+    bounds: Parameter,
+}
+
+/// The state of a seed search, which can be resumed from `next`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SeedSearchProgress {
+    /// Every seed of the range below this one has been checked.
+    pub next: i64,
+
+    /// The matching seeds below `next`, in ascending order.
+    pub found: Vec<i64>,
+}
+
+impl BiomeSeedSearch {
+    /// Searches for any of `biomes` within `radius` blocks of the origin, sampled every 16 blocks at
+    /// sea level. Uses every available core.
+    ///
+    /// Panics if `version` predates 1.18.
+    pub fn new(biomes: &[Biome], radius: u32, version: Version) -> Self {
+        assert!(version >= Version::V1_18, "multi noise biomes need 1.18 or later");
+
+        let parameters = overworld::parameters(version);
+        let union = |wanted: &dyn Fn(Biome) -> bool| {
+            parameters
+                .entries()
+                .iter()
+                .filter(|(_, biome)| wanted(*biome))
+                .map(|(point, _)| point.continentalness)
+                .reduce(Parameter::union)
+        };
+
+        let continentalness = union(&|biome| biomes.contains(&biome));
+        let bounds = union(&|_| true).unwrap();
+
+        BiomeSeedSearch {
+            biomes: biomes.to_vec(),
+            center: Point3D { x: 0, y: 64, z: 0 },
+            around_spawn: false,
+            radius,
+            scale: 16,
+            threads: thread::available_parallelism().map_or(1, |n| n.get()),
+            parameters,
+            continentalness,
+            bounds,
+        }
+    }
+
+    /// Searches around `center` instead, biomes are sampled on its `y` level.
+    pub fn with_center(mut self, center: BlockPoint) -> Self {
+        self.center = center;
+        self
+    }
+
+    /// Searches around the spawn of every seed instead, see `WorldSpawn`. Biomes are still sampled
+    /// on the `y` level of the center.
+    pub fn around_spawn(mut self) -> Self {
+        self.around_spawn = true;
+        self
+    }
+
+    /// Samples biomes every `scale` blocks instead. Smaller scales find smaller patches of biomes.
+    pub fn with_scale(mut self, scale: u32) -> Self {
+        assert!(scale > 0);
+
+        self.scale = scale;
+        self
+    }
+
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        assert!(threads > 0);
+
+        self.threads = threads;
+        self
+    }
+
+    /// Whether one of the biomes generates within the radius around the center in the world of
+    /// `seed`.
+    pub fn matches(&self, seed: i64) -> bool {
+        if self.around_spawn {
+            let sampler = ClimateSampler::new(seed);
+            let (x, z) = find_spawn(&sampler);
+            let candidates = self.candidates(sampler.continents(), (x >> 4) * 16 + 8, (z >> 4) * 16 + 8);
+
+            return self.any_wanted(&sampler, candidates);
+        }
+
+        // the rest of the climate is only built for seeds passing the filter
+        let continents = ContinentalnessSampler::new(seed);
+        let candidates = self.candidates(&continents, self.center.x, self.center.z);
+
+        !candidates.is_empty() && self.any_wanted(&continents.into_climate(), candidates)
+    }
+
+    /// The blocks around (`cx`, `cz`) whose continentalness fits one of the biomes.
+    fn candidates(&self, continents: &ContinentalnessSampler, cx: i32, cz: i32) -> Vec<(i32, i32)> {
+        let wanted = match self.continentalness {
+            Some(wanted) => wanted,
+            None => return Vec::new(),
+        };
+
+        let r = (self.radius / self.scale) as i32;
+        let s = self.scale as i32;
+
+        // values past the extremes of the biome parameters fit the biomes at the extremes best
+        (-r..=r)
+            .flat_map(|i| (-r..=r).map(move |j| (i, j)))
+            .filter(|(i, j)| i * i + j * j <= r * r)
+            .map(|(i, j)| (cx + i * s, cz + j * s))
+            .filter(|&(x, z)| {
+                // the column the biome is sampled at
+                let c = continents.continentalness(quart(x) << 2, quart(z) << 2);
+                wanted.contains(quantize(c as f32).clamp(self.bounds.min, self.bounds.max))
+            })
+            .collect()
+    }
+
+    fn any_wanted(&self, sampler: &ClimateSampler, candidates: Vec<(i32, i32)>) -> bool {
+        let qy = quart(self.center.y);
+
+        candidates.into_iter().any(|(x, z)| {
+            let biome = self.parameters.find_value(&sampler.sample(quart(x), qy, quart(z)));
+            self.biomes.contains(&biome)
+        })
+    }
+
+    /// Checks every seed of `seeds` on all threads, until done or until `stop` is set. Search the
+    /// rest later by resuming from `next` of the returned progress.
+    pub fn run(&self, seeds: Range<i64>, stop: &AtomicBool) -> SeedSearchProgress {
+        let len = (seeds.end as i128 - seeds.start as i128).max(0) as u64;
+        let offset = AtomicU64::new(0);
+
+        // the offset of every checked batch, along with the matching seeds in it
+        let batches: Mutex<Vec<(u64, Vec<i64>)>> = Mutex::new(Vec::new());
+
+        thread::scope(|scope| {
+            for _ in 0..self.threads {
+                scope.spawn(|| {
+                    while !stop.load(Ordering::Relaxed) {
+                        let first = offset.fetch_add(SEED_BATCH, Ordering::Relaxed);
+                        if first >= len {
+                            break;
+                        }
+
+                        let found = (first..len.min(first + SEED_BATCH))
+                            .map(|i| (seeds.start as i128 + i as i128) as i64)
+                            .filter(|&seed| self.matches(seed))
+                            .collect();
+
+                        batches.lock().unwrap().push((first, found));
+                    }
+                });
+            }
+        });
+
+        let mut batches = batches.into_inner().unwrap();
+        batches.sort_unstable_by_key(|(first, _)| *first);
+
+        // batches may finish out of order, only those before the first gap count as done
+        let mut next = 0;
+        let mut found = Vec::new();
+        for (first, seeds) in batches {
+            if first != next {
+                break;
+            }
+
+            next = len.min(first + SEED_BATCH);
+            found.extend(seeds);
+        }
+
+        SeedSearchProgress {
+            next: (seeds.start as i128 + next as i128) as i64,
+            found,
+        }
+    }
+}
+
+/// Returns the squared distance to `center` and the bounds of the closest square in `map` of at
+/// least `size` blocks covered by `biomes`.
+fn nearest_square(map: &BiomeMap, center: BlockPoint, biomes: &[Biome], size: u32) -> Option<(i64, BoundingBox)> {
+    let (width, depth) = map.biomes.dim();
+    let s = map.scale as i32;
+
+    // the number of cells along each side of a large enough square
+    let cells = size.div_ceil(map.scale) as usize;
+
+    // the side of the largest square of matching cells ending at each cell
+    let mut sides: Array2<usize> = Array2::zeros((width + 1, depth + 1));
+    let mut best: Option<(i64, BoundingBox)> = None;
+
+    for i in 1..=width {
+        for j in 1..=depth {
+            if !biomes.contains(&map.biomes[[i - 1, j - 1]]) {
+                continue;
+            }
+
+            let side = 1 + sides[[i - 1, j]].min(sides[[i, j - 1]]).min(sides[[i - 1, j - 1]]);
+            sides[[i, j]] = side;
+
+            if side < cells {
+                continue;
+            }
+
+            let min = map.block_at(i - cells, j - cells);
+            let max = map.block_at(i - 1, j - 1);
+            let area = BoundingBox::new(
+                min,
+                Point3D {
+                    x: max.x + s - 1,
+                    z: max.z + s - 1,
+                    ..max
+                },
+            );
+
+            let dx = (area.min.x as i64 + area.max.x as i64) / 2 - center.x as i64;
+            let dz = (area.min.z as i64 + area.max.z as i64) / 2 - center.z as i64;
+            let dist = dx * dx + dz * dz;
+
+            if !matches!(best, Some((d, _)) if d <= dist) {
+                best = Some((dist, area));
+            }
+        }
+    }
+
+    best
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::AtomicBool;
+
+    use crate::biome::overworld::ContinentalnessSampler;
+    use crate::biome::{quart, Biome, BiomeSource, WorldSpawn};
+    use crate::rand::{flower_at, FlowerForestFlower};
+    use crate::util::{Point3D, Version};
+
+    use super::{nearest_biome_area, search_flower_forest, BiomeSeedSearch, SeedSearchProgress};
+
+    // a flower forest of 64 by 64 blocks at (96, 96) and one of 256 by 256 blocks at (-512, 256),
+    // in a world of plains
+    struct Forests;
+
+    impl BiomeSource for Forests {
+        fn biome_at_quart(&self, qx: i32, _qy: i32, qz: i32) -> Biome {
+            let (x, z) = (qx * 4, qz * 4);
+
+            if (96..160).contains(&x) && (96..160).contains(&z) || (-512..-256).contains(&x) && (256..512).contains(&z)
+            {
+                Biome::FlowerForest
+            } else {
+                Biome::Plains
+            }
+        }
+    }
+
+    #[test]
+    fn test_nearest_biome_area() {
+        let origin = Point3D { x: 0, y: 64, z: 0 };
+        let biomes = [Biome::FlowerForest];
+
+        let small = nearest_biome_area(&Forests, origin, &biomes, 64, 1024, 16).unwrap();
+        assert_eq!(small.min, Point3D { x: 96, y: 64, z: 96 });
+        assert_eq!(small.max, Point3D { x: 159, y: 64, z: 159 });
+
+        // only the far forest is large enough
+        let large = nearest_biome_area(&Forests, origin, &biomes, 100, 1024, 4).unwrap();
+        assert!(large.width() >= 100 && large.depth() >= 100);
+        assert!(large.min.x >= -512 && large.max.x < -256);
+        assert!(large.min.z >= 256 && large.max.z < 512);
+        // the corner closest to the origin
+        assert_eq!((large.max.x, large.min.z), (-257, 256));
+
+        assert_eq!(nearest_biome_area(&Forests, origin, &biomes, 100, 256, 4), None);
+        assert_eq!(nearest_biome_area(&Forests, origin, &[Biome::Desert], 4, 64, 4), None);
+    }
+
+    #[test]
+    fn test_search_flower_forest() {
+        let origin = Point3D { x: 0, y: 64, z: 0 };
+        let flowers = [FlowerForestFlower::AzureBluet, FlowerForestFlower::RedTulip];
+
+        let (area, results) = search_flower_forest(&Forests, origin, 32, 256, 24, &flowers).unwrap();
+
+        assert_eq!(area.width(), 32);
+        assert!(!results.is_empty());
+
+        for res in &results {
+            assert!(area.contains(&res.center));
+
+            for (flower, p) in &res.flowers {
+                assert_eq!(flower_at(*p), *flower);
+                assert_eq!(Forests.biome_at(*p), Biome::FlowerForest);
+            }
+        }
+    }
+
+    #[test]
+    fn test_biome_seed_search() {
+        let search = BiomeSeedSearch::new(&[Biome::MushroomFields], 1500, Version::V1_20)
+            .with_scale(64)
+            .with_threads(3);
+
+        let progress = |next, found: &[i64]| SeedSearchProgress {
+            next,
+            found: found.to_vec(),
+        };
+
+        assert_eq!(search.run(0..40, &AtomicBool::new(false)), progress(40, &[0, 11, 14]));
+        assert!(search.matches(11));
+        assert!(!search.matches(12));
+
+        // a stopped search resumes where it stopped
+        assert_eq!(search.run(0..40, &AtomicBool::new(true)), progress(0, &[]));
+        assert_eq!(search.run(12..40, &AtomicBool::new(false)), progress(40, &[14]));
+
+        // the filter samples the quarts the biomes are sampled at
+        let coasts = BiomeSeedSearch::new(&[Biome::Beach], 256, Version::V1_20).with_scale(4);
+        for seed in 0..4 {
+            let continents = ContinentalnessSampler::new(seed);
+            let columns = |cx, cz| {
+                coasts
+                    .candidates(&continents, cx, cz)
+                    .into_iter()
+                    .map(|(x, z)| (quart(x), quart(z)))
+                    .collect::<Vec<_>>()
+            };
+
+            assert_eq!(columns(3, 2), columns(0, 0));
+        }
+
+        // the same as searching around the predicted spawn
+        let plains = BiomeSeedSearch::new(&[Biome::Plains], 32, Version::V1_20).with_scale(8);
+        for seed in [42, 123] {
+            let spawn = WorldSpawn::new(seed, Version::V1_20).spawn;
+            let center = plains.clone().with_center(Point3D { y: 64, ..spawn });
+
+            assert_eq!(plains.clone().around_spawn().matches(seed), center.matches(seed));
+        }
+
+        // the end never generates in the overworld
+        let never = BiomeSeedSearch::new(&[Biome::TheEnd], 1500, Version::V1_20);
+        assert!(!never.matches(0));
+    }
+}
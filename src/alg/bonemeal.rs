@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+
+use crate::rand::{flowers_at, Flower, FlowerBiome};
+use crate::util::{BlockPoint, Point3D};
+
+/// The number of placement attempts made by a single bonemeal use on grass.
+const ATTEMPTS: i32 = 128;
+
+/// Every 16 attempts, the random walk of an attempt gets one step longer.
+const ATTEMPTS_PER_STEP: i32 = 16;
+
+/// The chance (`1 / n`) for an attempt landing on air to place a flower instead of grass.
+const FLOWER_CHANCE: i32 = 8;
+
+/// The chance (`1 / n`) for an attempt landing on short grass to grow it into tall grass.
+const TALL_GRASS_CHANCE: i32 = 10;
+
+/// The outcome of bonemealing the same grass block many times.
+pub struct BonemealResult {
+    /// The grass block which was bonemealed.
+    pub center: BlockPoint,
+    pub biome: FlowerBiome,
+    pub trials: u32,
+
+    /// The expected number of each block placed at each position per bonemeal use.
+    pub blocks: HashMap<BlockPoint, HashMap<Flower, f64>>,
+}
+
+impl BonemealResult {
+    /// The expected number of each block placed in the whole area per bonemeal use.
+    pub fn totals(&self) -> HashMap<Flower, f64> {
+        let mut totals = HashMap::new();
+
+        for (flower, count) in self.blocks.values().flatten() {
+            *totals.entry(*flower).or_insert(0f64) += count;
+        }
+
+        totals
+    }
+}
+
+/// Simulates `trials` bonemeal uses on the grass block `center`, each on a fresh platform of grass
+/// blocks with nothing above them. The platform is the square of grass blocks within `radius`
+/// blocks of `center` (on both axes), at the same `y` level.
+///
+/// The random walk of every attempt is modeled after `GrassBlock.performBonemeal` and each flower is
+/// picked according to `flowers_at` for the given biome. A `radius` of `7` or more covers every
+/// block an attempt can reach.
+pub fn simulate_bonemeal(
+    center: BlockPoint,
+    radius: u32,
+    biome: FlowerBiome,
+    trials: u32,
+    seed: u64,
+) -> BonemealResult {
+    let mut rnd = java_rand::Random::new(seed);
+    let mut counts: HashMap<BlockPoint, HashMap<Flower, u32>> = HashMap::new();
+
+    let r = radius as i32;
+    let is_grass = |p: &BlockPoint| (p.x - center.x).abs() <= r && (p.z - center.z).abs() <= r;
+
+    for _ in 0..trials {
+        let mut placed: HashMap<BlockPoint, Flower> = HashMap::new();
+
+        'attempt: for i in 0..ATTEMPTS {
+            // attempts start on the block above the bonemealed grass
+            let mut p = Point3D { x: center.x, y: center.y + 1, z: center.z };
+
+            for _ in 0..(i / ATTEMPTS_PER_STEP) {
+                let dx = rnd.next_i32_bound(3) - 1;
+                let dy = (rnd.next_i32_bound(3) - 1) * rnd.next_i32_bound(3) / 2;
+                let dz = rnd.next_i32_bound(3) - 1;
+
+                p = Point3D { x: p.x + dx, y: p.y + dy, z: p.z + dz };
+
+                // on a flat platform, stepping up or down always leaves the grass
+                let below = Point3D { x: p.x, y: p.y - 1, z: p.z };
+                if p.y != center.y + 1 || !is_grass(&below) {
+                    continue 'attempt;
+                }
+            }
+
+            match placed.get(&p) {
+                Some(Flower::ShortGrass) => {
+                    if rnd.next_i32_bound(TALL_GRASS_CHANCE) == 0 {
+                        placed.insert(p, Flower::TallGrass);
+                    }
+                }
+                Some(_) => {}
+                None => {
+                    let block = if rnd.next_i32_bound(FLOWER_CHANCE) == 0 {
+                        pick_flower(&mut rnd, flowers_at(p, biome))
+                    } else {
+                        Flower::ShortGrass
+                    };
+
+                    placed.insert(p, block);
+                }
+            }
+        }
+
+        for (p, block) in placed {
+            *counts.entry(p).or_default().entry(block).or_insert(0) += 1;
+        }
+    }
+
+    let blocks = counts
+        .into_iter()
+        .map(|(p, blocks)| {
+            let blocks = blocks
+                .into_iter()
+                .map(|(block, count)| (block, count as f64 / trials as f64))
+                .collect();
+
+            (p, blocks)
+        })
+        .collect();
+
+    BonemealResult { center, biome, trials, blocks }
+}
+
+/// Picks a flower out of a distribution returned by `flowers_at`.
+fn pick_flower(rnd: &mut java_rand::Random, flowers: Vec<(Flower, f64)>) -> Flower {
+    let mut roll = rnd.next_f64();
+
+    for (flower, chance) in &flowers {
+        if roll < *chance {
+            return *flower;
+        }
+
+        roll -= chance;
+    }
+
+    // rounding errors can leave a tiny bit of the roll unaccounted for
+    flowers[flowers.len() - 1].0
+}
+
+#[cfg(test)]
+mod test {
+    use crate::rand::{Flower, FlowerBiome};
+    use crate::util::Point3D;
+
+    use super::simulate_bonemeal;
+
+    #[test]
+    fn test_single_grass_block() {
+        let center = Point3D { x: 0, y: 64, z: 0 };
+        let res = simulate_bonemeal(center, 0, FlowerBiome::Swamp, 100, 0);
+
+        assert_eq!(res.blocks.len(), 1);
+        assert!(res.blocks.contains_key(&Point3D { x: 0, y: 65, z: 0 }));
+
+        let total: f64 = res.totals().values().sum();
+        assert!((total - 1f64).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_swamp_flowers() {
+        let center = Point3D { x: 100, y: 64, z: -100 };
+        let res = simulate_bonemeal(center, 7, FlowerBiome::Swamp, 200, 1);
+        let totals = res.totals();
+
+        assert!(totals.keys().all(|f| matches!(f, Flower::BlueOrchid | Flower::ShortGrass | Flower::TallGrass)));
+        assert!(totals[&Flower::BlueOrchid] > 0f64);
+        assert!(totals[&Flower::ShortGrass] > totals[&Flower::BlueOrchid]);
+
+        for p in res.blocks.keys() {
+            assert_eq!(p.y, 65);
+            assert!((p.x - center.x).abs() <= 7 && (p.z - center.z).abs() <= 7);
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use std::fmt::Display;
+
+use ndarray::Array2;
+
+use crate::rand::{flower_at, FlowerForestFlower};
+use crate::util::{BlockPoint, Point3D};
+
+/// The squared distance to, and grid position of, the closest matching cell.
+type Closest = Option<(i64, (usize, usize))>;
+
+/// A candidate position from which every requested flower type can be reached.
+#[derive(Debug, PartialEq)]
+pub struct FlowerSearchResult {
+    pub center: BlockPoint,
+
+    /// The smallest euclidean radius (in blocks) around `center` containing every requested flower.
+    pub radius: u32,
+
+    /// The closest block to `center` for each requested flower type.
+    pub flowers: Vec<(FlowerForestFlower, BlockPoint)>,
+}
+
+impl Display for FlowerSearchResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Center: (x: {}, y: {}, z: {}) | Radius: {}", self.center.x, self.center.y, self.center.z, self.radius)?;
+
+        for (flower, p) in &self.flowers {
+            writeln!(f, "  {:?}: (x: {}, y: {}, z: {})", flower, p.x, p.y, p.z)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Searches every center in the rectangular area outlined by `start` and `end` for positions where
+/// each flower in `flowers` is within `max_radius` blocks. Both points must be on the same `y`
+/// level.
+///
+/// Results are sorted by ascending radius. Pass `&FlowerForestFlower::ALL` to require every flower.
+pub fn search_all_flowers(
+    start: BlockPoint,
+    end: BlockPoint,
+    max_radius: u32,
+    flowers: &[FlowerForestFlower],
+) -> Vec<FlowerSearchResult> {
+    assert!(start.x <= end.x);
+    assert!(start.z <= end.z);
+    assert_eq!(start.y, end.y);
+
+    let y = start.y;
+    let r = max_radius as i32;
+
+    // the flower matrix has to cover every block reachable from the edges of the search area
+    let origin_x = start.x - r;
+    let origin_z = start.z - r;
+    let grid_width = (end.x - start.x + 1 + 2 * r) as usize;
+    let grid_height = (end.z - start.z + 1 + 2 * r) as usize;
+
+    let grid: Array2<FlowerForestFlower> = Array2::from_shape_fn((grid_width, grid_height), |(i, j)| {
+        flower_at(Point3D {
+            x: origin_x + i as i32,
+            y,
+            z: origin_z + j as i32,
+        })
+    });
+
+    // squared distance to, and position of, the closest block of each requested flower
+    let transforms: Vec<Array2<Closest>> = flowers
+        .iter()
+        .map(|flower| distance_transform(&grid.map(|f| f == flower)))
+        .collect();
+
+    let max_dist = (r as i64) * (r as i64);
+    let mut results = Vec::new();
+
+    for x in start.x..=end.x {
+        for z in start.z..=end.z {
+            let i = (x - origin_x) as usize;
+            let j = (z - origin_z) as usize;
+
+            let closest: Option<Vec<(i64, (usize, usize))>> = transforms
+                .iter()
+                .map(|t| t[[i, j]].filter(|(d, _)| *d <= max_dist))
+                .collect();
+
+            let Some(closest) = closest else {
+                continue;
+            };
+
+            let radius = closest.iter().map(|(d, _)| *d).max().unwrap_or(0);
+
+            results.push(FlowerSearchResult {
+                center: Point3D { x, y, z },
+                radius: (radius as f64).sqrt().ceil() as u32,
+                flowers: flowers
+                    .iter()
+                    .zip(closest)
+                    .map(|(flower, (_, (i, j)))| {
+                        let p = Point3D {
+                            x: origin_x + i as i32,
+                            y,
+                            z: origin_z + j as i32,
+                        };
+
+                        (*flower, p)
+                    })
+                    .collect(),
+            });
+        }
+    }
+
+    results.sort_by_key(|res| res.radius);
+    results
+}
+
+/// Computes the exact squared euclidean distance from every cell of `mask` to its closest `true`
+/// cell, along with the position of that cell.
+///
+/// see: Felzenszwalb & Huttenlocher, Distance Transforms of Sampled Functions
+fn distance_transform(mask: &Array2<bool>) -> Array2<Closest> {
+    let (width, height) = mask.dim();
+
+    // closest `true` cell along each column
+    let mut cols: Array2<Option<(i64, usize)>> = Array2::from_elem((width, height), None);
+    for i in 0..width {
+        let f: Vec<Option<i64>> = (0..height).map(|j| mask[[i, j]].then_some(0)).collect();
+
+        for (j, v) in distance_transform_1d(&f).into_iter().enumerate() {
+            cols[[i, j]] = v;
+        }
+    }
+
+    // closest column minimum along each row
+    let mut res = Array2::from_elem((width, height), None);
+    for j in 0..height {
+        let f: Vec<Option<i64>> = (0..width).map(|i| cols[[i, j]].map(|(d, _)| d)).collect();
+
+        for (i, v) in distance_transform_1d(&f).into_iter().enumerate() {
+            res[[i, j]] = v.map(|(d, ci)| (d, (ci, cols[[ci, j]].unwrap().1)));
+        }
+    }
+
+    res
+}
+
+/// Computes `min_q (p - q)^2 + f(q)` for every `p`, along with the minimizing `q`. `None` values of
+/// `f` are treated as infinite.
+fn distance_transform_1d(f: &[Option<i64>]) -> Vec<Option<(i64, usize)>> {
+    let qs: Vec<(usize, i64)> = f.iter().enumerate().filter_map(|(q, v)| v.map(|v| (q, v))).collect();
+
+    if qs.is_empty() {
+        return vec![None; f.len()];
+    }
+
+    // intersection of the parabolas rooted at `a` and `b`
+    let intersect = |(a, fa): (usize, i64), (b, fb): (usize, i64)| -> f64 {
+        let (a, b) = (a as i64, b as i64);
+        ((fb + b * b) - (fa + a * a)) as f64 / (2 * (b - a)) as f64
+    };
+
+    // lower envelope of the parabolas
+    let mut v: Vec<(usize, i64)> = vec![qs[0]];
+    let mut z: Vec<f64> = vec![f64::NEG_INFINITY, f64::INFINITY];
+
+    for &q in &qs[1..] {
+        let mut s = intersect(v[v.len() - 1], q);
+
+        while s <= z[v.len() - 1] {
+            v.pop();
+            z.pop();
+            s = intersect(v[v.len() - 1], q);
+        }
+
+        v.push(q);
+        *z.last_mut().unwrap() = s;
+        z.push(f64::INFINITY);
+    }
+
+    let mut k = 0;
+    (0..f.len())
+        .map(|p| {
+            while z[k + 1] < p as f64 {
+                k += 1;
+            }
+
+            let (q, fq) = v[k];
+            let d = p as i64 - q as i64;
+
+            Some((d * d + fq, q))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::rand::{flower_at, FlowerForestFlower};
+    use crate::util::Point3D;
+
+    use super::search_all_flowers;
+
+    #[test]
+    fn test_search_single_flower() {
+        let p = Point3D { x: -53, y: -60, z: 103 };
+        let res = search_all_flowers(p, p, 0, &[FlowerForestFlower::RedTulip]);
+
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].radius, 0);
+        assert_eq!(res[0].flowers, vec![(FlowerForestFlower::RedTulip, p)]);
+    }
+
+    #[test]
+    fn test_search_all_flowers() {
+        let start = Point3D { x: 50, y: -60, z: 295 };
+        let end = Point3D { x: 58, y: -60, z: 303 };
+        let res = search_all_flowers(start, end, 16, &FlowerForestFlower::ALL);
+
+        assert_eq!(res[0].center, Point3D { x: 54, y: -60, z: 299 });
+        assert_eq!(res[0].radius, 15);
+        assert!(res.windows(2).all(|w| w[0].radius <= w[1].radius));
+
+        for res in &res {
+            for (flower, p) in &res.flowers {
+                let (dx, dz) = (p.x - res.center.x, p.z - res.center.z);
+
+                assert_eq!(flower_at(*p), *flower);
+                assert!(((dx * dx + dz * dz) as f64).sqrt() <= res.radius as f64);
+            }
+        }
+    }
+}
@@ -0,0 +1,311 @@
+use crate::rand::lcg::MASK;
+use crate::rand::source::{LegacyRandomSource, RandomSource};
+use crate::structure::{RandomSpreadPlacement, StructureSet};
+use crate::util::{BlockPoint, BoundingBox, ChunkPoint, Point3D, Version};
+
+/// Mobs only spawn within this many blocks of a player.
+pub const AFK_RADIUS: f64 = 128.0;
+
+// the size of a swamp hut facing north or south, it swaps width and depth otherwise
+const HUT_WIDTH: i32 = 7;
+const HUT_HEIGHT: i32 = 7;
+const HUT_DEPTH: i32 = 9;
+
+// huts are placed at this height before being moved onto the terrain
+const HUT_Y: i32 = 64;
+
+/// A group of swamp huts which can all be loaded from a single AFK spot.
+#[derive(Clone, Debug)]
+pub struct HutCluster {
+    pub seed: i64,
+
+    /// The block to AFK at, at the nominal height of the huts.
+    pub afk: BlockPoint,
+
+    /// Horizontal distance from the center of `afk` to the furthest corner of any hut.
+    pub radius: f64,
+    pub huts: Vec<BoundingBox>,
+}
+
+impl std::fmt::Display for HutCluster {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} huts (seed: {}, afk: {}, radius: {:.2})",
+            self.huts.len(),
+            self.seed,
+            self.afk,
+            self.radius
+        )
+    }
+}
+
+/// Returns the bounding box of a swamp hut generated in chunk `c`, before it is moved onto the
+/// terrain. Only the rotation of the hut depends on the `seed`.
+pub fn hut_box(seed: i64, c: ChunkPoint) -> BoundingBox {
+    let mut rnd = LegacyRandomSource::large_feature(seed, c.x, c.z);
+
+    // north and south are the even directions
+    let (dx, dz) = if rnd.next_i32_bound(4) % 2 == 0 {
+        (HUT_WIDTH, HUT_DEPTH)
+    } else {
+        (HUT_DEPTH, HUT_WIDTH)
+    };
+
+    let min: BlockPoint = c.into();
+
+    BoundingBox::new(
+        Point3D { y: HUT_Y, ..min },
+        Point3D {
+            x: min.x + dx - 1,
+            y: HUT_Y + HUT_HEIGHT - 1,
+            z: min.z + dz - 1,
+        },
+    )
+}
+
+/// Searches the regions overlapping the rectangular area outlined by `start` and `end` for groups of
+/// at least `min_huts` (3 or 4) swamp hut attempts within `AFK_RADIUS` of a single spot. The biomes
+/// of the attempts are not checked.
+///
+/// Clusters with more huts come first, then the tighter ones.
+pub fn search_regions(seed: i64, start: ChunkPoint, end: ChunkPoint, min_huts: usize) -> Vec<HutCluster> {
+    assert!(start.x <= end.x);
+    assert!(start.z <= end.z);
+    assert!(min_huts == 3 || min_huts == 4, "clusters have 3 or 4 huts");
+
+    let placement = hut_placement();
+    let (rx0, rz0) = placement.region(start);
+    let (rx1, rz1) = placement.region(end);
+
+    // huts in a cluster are at most 256 blocks apart, so every cluster fits a 2x2 window of regions
+    let mut clusters: Vec<HutCluster> = (rx0..rx1)
+        .flat_map(|rx| (rz0..rz1).map(move |rz| (rx, rz)))
+        .filter_map(|(rx, rz)| search_window(&placement, seed, rx, rz, min_huts))
+        .collect();
+
+    clusters.sort_by(|a, b| b.huts.len().cmp(&a.huts.len()).then(a.radius.total_cmp(&b.radius)));
+    clusters
+}
+
+/// Checks every seed of `seeds` for a cluster of at least `min_huts` swamp huts around the origin,
+/// in the four regions touching it.
+///
+/// Structure placement only depends on the lower 48 bits of the seed, so only structure seeds need
+/// to be searched. Every result holds for all the world seeds returned by `world_seeds`.
+pub fn search_structure_seeds<I>(seeds: I, min_huts: usize) -> Vec<HutCluster>
+where
+    I: IntoIterator<Item = i64>,
+{
+    assert!(min_huts == 3 || min_huts == 4, "clusters have 3 or 4 huts");
+
+    let placement = hut_placement();
+
+    seeds
+        .into_iter()
+        .filter_map(|seed| search_window(&placement, seed & MASK, -1, -1, min_huts))
+        .collect()
+}
+
+/// Returns the `2^16` world seeds sharing the lower 48 bits of `structure_seed`.
+pub fn world_seeds(structure_seed: i64) -> impl Iterator<Item = i64> {
+    (0..1i64 << 16).map(move |high| (high << 48) | (structure_seed & MASK))
+}
+
+fn hut_placement() -> RandomSpreadPlacement {
+    // swamp huts are spread the same way in every supported version
+    StructureSet::SwampHut.placement(Version::V1_13).unwrap()
+}
+
+/// Looks for the best cluster in the 2x2 regions starting at region (`rx`, `rz`).
+fn search_window(placement: &RandomSpreadPlacement, seed: i64, rx: i32, rz: i32, min_huts: usize) -> Option<HutCluster> {
+    let chunks = [
+        placement.potential_chunk(seed, rx, rz),
+        placement.potential_chunk(seed, rx + 1, rz),
+        placement.potential_chunk(seed, rx, rz + 1),
+        placement.potential_chunk(seed, rx + 1, rz + 1),
+    ];
+
+    // cheap rejection: every hut has to be close to the shared corner
+    let corner = ChunkPoint {
+        x: (rx + 1) * placement.spacing,
+        z: (rz + 1) * placement.spacing,
+    };
+    let near = chunks
+        .iter()
+        .filter(|c| (c.x - corner.x).abs() <= 16 && (c.z - corner.z).abs() <= 16)
+        .count();
+
+    if near < min_huts {
+        return None;
+    }
+
+    let huts: Vec<BoundingBox> = chunks.iter().map(|c| hut_box(seed, *c)).collect();
+
+    if let Some(cluster) = fit_cluster(seed, &huts) {
+        return Some(cluster);
+    }
+
+    if min_huts > 3 {
+        return None;
+    }
+
+    (0..4)
+        .filter_map(|skip| {
+            let triple: Vec<BoundingBox> = huts
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != skip)
+                .map(|(_, b)| *b)
+                .collect();
+
+            fit_cluster(seed, &triple)
+        })
+        .min_by(|a, b| a.radius.total_cmp(&b.radius))
+}
+
+/// Finds an AFK spot covering every hut in `huts`, if there is one.
+fn fit_cluster(seed: i64, huts: &[BoundingBox]) -> Option<HutCluster> {
+    // mobs spawn in the center of the blocks
+    let corners: Vec<(f64, f64)> = huts
+        .iter()
+        .flat_map(|b| {
+            let (x0, x1) = (b.min.x as f64 + 0.5, b.max.x as f64 + 0.5);
+            let (z0, z1) = (b.min.z as f64 + 0.5, b.max.z as f64 + 0.5);
+
+            vec![(x0, z0), (x0, z1), (x1, z0), (x1, z1)]
+        })
+        .collect();
+
+    let ((cx, cz), r) = enclosing_circle(&corners);
+    if r > AFK_RADIUS {
+        return None;
+    }
+
+    let afk = Point3D {
+        x: cx.floor() as i32,
+        y: HUT_Y,
+        z: cz.floor() as i32,
+    };
+    let radius = max_distance(&corners, (afk.x as f64 + 0.5, afk.z as f64 + 0.5));
+
+    if radius > AFK_RADIUS {
+        return None;
+    }
+
+    Some(HutCluster {
+        seed,
+        afk,
+        radius,
+        huts: huts.to_vec(),
+    })
+}
+
+fn max_distance(points: &[(f64, f64)], c: (f64, f64)) -> f64 {
+    points
+        .iter()
+        .map(|p| (p.0 - c.0).hypot(p.1 - c.1))
+        .fold(0f64, f64::max)
+}
+
+/// The smallest circle holding all `points`. It passes through two or three of them, which are few
+/// enough to try all combinations.
+fn enclosing_circle(points: &[(f64, f64)]) -> ((f64, f64), f64) {
+    let mut best = ((0f64, 0f64), f64::INFINITY);
+
+    let mut consider = |c: (f64, f64)| {
+        let r = max_distance(points, c);
+        if r < best.1 {
+            best = (c, r);
+        }
+    };
+
+    for (i, a) in points.iter().enumerate() {
+        for (j, b) in points.iter().enumerate().skip(i + 1) {
+            consider(((a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0));
+
+            for c in points.iter().skip(j + 1) {
+                if let Some(center) = circumcenter(*a, *b, *c) {
+                    consider(center);
+                }
+            }
+        }
+    }
+
+    best
+}
+
+fn circumcenter(a: (f64, f64), b: (f64, f64), c: (f64, f64)) -> Option<(f64, f64)> {
+    let d = 2.0 * (a.0 * (b.1 - c.1) + b.0 * (c.1 - a.1) + c.0 * (a.1 - b.1));
+
+    // collinear points
+    if d.abs() < 1e-9 {
+        return None;
+    }
+
+    let (a2, b2, c2) = (a.0 * a.0 + a.1 * a.1, b.0 * b.0 + b.1 * b.1, c.0 * c.0 + c.1 * c.1);
+
+    Some((
+        (a2 * (b.1 - c.1) + b2 * (c.1 - a.1) + c2 * (a.1 - b.1)) / d,
+        (a2 * (c.0 - b.0) + b2 * (a.0 - c.0) + c2 * (b.0 - a.0)) / d,
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::util::{ChunkPoint, Point3D};
+
+    use super::{enclosing_circle, search_regions, search_structure_seeds, world_seeds, AFK_RADIUS};
+
+    #[test]
+    fn test_enclosing_circle() {
+        let square = [(0.0, 0.0), (2.0, 0.0), (0.0, 2.0), (2.0, 2.0)];
+        let ((x, z), r) = enclosing_circle(&square);
+
+        assert!((x - 1.0).abs() < 1e-9 && (z - 1.0).abs() < 1e-9);
+        assert!((r - 2f64.sqrt()).abs() < 1e-9);
+
+        let triangle = [(0.0, 0.0), (4.0, 0.0), (2.0, 1.0)];
+        assert_eq!(enclosing_circle(&triangle), ((2.0, 0.0), 2.0));
+    }
+
+    #[test]
+    fn test_search_structure_seeds() {
+        let clusters = search_structure_seeds(0..60_000, 3);
+
+        assert_eq!(clusters.len(), 2);
+        assert_eq!(clusters[0].seed, 25832);
+        assert_eq!(clusters[0].afk, Point3D { x: -68, y: 64, z: -45 });
+
+        for cluster in &clusters {
+            assert_eq!(cluster.huts.len(), 3);
+            assert!(cluster.radius <= AFK_RADIUS);
+
+            for hut in &cluster.huts {
+                assert_eq!(hut.width() * hut.depth(), 63);
+
+                for &(x, z) in &[(hut.min.x, hut.min.z), (hut.max.x, hut.max.z)] {
+                    let dx = (x - cluster.afk.x) as f64;
+                    let dz = (z - cluster.afk.z) as f64;
+                    assert!(dx.hypot(dz) <= AFK_RADIUS);
+                }
+            }
+        }
+
+        assert!(search_structure_seeds(0..60_000, 4).is_empty());
+    }
+
+    #[test]
+    fn test_world_seeds() {
+        let seed = world_seeds(25832).nth(1234).unwrap();
+        assert_eq!(seed & ((1 << 48) - 1), 25832);
+
+        let start = ChunkPoint { x: -64, z: -64 };
+        let end = ChunkPoint { x: 63, z: 63 };
+        let clusters = search_regions(seed, start, end, 3);
+
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(clusters[0].afk, Point3D { x: -68, y: 64, z: -45 });
+        assert_eq!(clusters[0].huts, search_structure_seeds(Some(25832), 3)[0].huts);
+    }
+}
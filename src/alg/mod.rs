@@ -1,5 +1,9 @@
-/// This module provides basic algorithm for working with the functions provided by the `rand`
-/// module.
+//! This module provides basic algorithm for working with the functions provided by the `rand`
+//! module.
 
 pub mod util;
 pub mod slime;
+pub mod flowers;
+pub mod bonemeal;
+pub mod huts;
+pub mod biomes;
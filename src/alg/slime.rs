@@ -95,9 +95,9 @@ pub fn search_rect_chunked(
 
     println!("{start} {end}");
 
-    let chunks: Vec<(ChunkPoint, ChunkPoint)> = (0..((w + CHUNK_SIZE - 1) / CHUNK_SIZE))
+    let chunks: Vec<(ChunkPoint, ChunkPoint)> = (0..w.div_ceil(CHUNK_SIZE))
         .flat_map(|x| {
-            (0..((h + CHUNK_SIZE - 1) / CHUNK_SIZE)).map(move |z| {
+            (0..h.div_ceil(CHUNK_SIZE)).map(move |z| {
                 let p1 = ChunkPoint {
                     x: (x * CHUNK_SIZE) as i32 + start.x,
                     z: (z * CHUNK_SIZE) as i32 + start.z,
@@ -42,7 +42,7 @@ impl SearchResult {
 
 impl PartialOrd for SearchResult {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.slime_chunks.cmp(&other.slime_chunks))
+        Some(self.cmp(other))
     }
 }
 
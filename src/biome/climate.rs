@@ -0,0 +1,206 @@
+//! The climate parameters biomes are picked by, as `Climate` in the game.
+
+/// Converts a climate value to the fixed point representation used for comparisons.
+pub fn quantize(v: f32) -> i64 {
+    (v * 10000f32) as i64
+}
+
+/// An inclusive range of quantized climate values.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Parameter {
+    pub min: i64,
+    pub max: i64,
+}
+
+impl Parameter {
+    pub fn point(v: f32) -> Self {
+        Parameter::span(v, v)
+    }
+
+    /// Panics if `min > max`.
+    pub fn span(min: f32, max: f32) -> Self {
+        assert!(min <= max, "min > max: {} {}", min, max);
+
+        Parameter {
+            min: quantize(min),
+            max: quantize(max),
+        }
+    }
+
+    /// The smallest range covering both `self` and `other`.
+    pub fn union(self, other: Parameter) -> Self {
+        Parameter {
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+        }
+    }
+
+    /// How far the quantized value `v` is from the range, `0` if it is inside.
+    pub fn distance(&self, v: i64) -> i64 {
+        (v - self.max).max(self.min - v).max(0)
+    }
+
+    pub fn contains(&self, v: i64) -> bool {
+        self.distance(v) == 0
+    }
+}
+
+/// The quantized climate of a position.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct TargetPoint {
+    pub temperature: i64,
+    pub humidity: i64,
+    pub continentalness: i64,
+    pub erosion: i64,
+    pub depth: i64,
+    pub weirdness: i64,
+}
+
+impl TargetPoint {
+    pub fn new(
+        temperature: f32,
+        humidity: f32,
+        continentalness: f32,
+        erosion: f32,
+        depth: f32,
+        weirdness: f32,
+    ) -> Self {
+        TargetPoint {
+            temperature: quantize(temperature),
+            humidity: quantize(humidity),
+            continentalness: quantize(continentalness),
+            erosion: quantize(erosion),
+            depth: quantize(depth),
+            weirdness: quantize(weirdness),
+        }
+    }
+}
+
+/// The climate a biome is picked for. `offset` is a penalty added to the distance of every target,
+/// making rare biomes rarer.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct ParameterPoint {
+    pub temperature: Parameter,
+    pub humidity: Parameter,
+    pub continentalness: Parameter,
+    pub erosion: Parameter,
+    pub depth: Parameter,
+    pub weirdness: Parameter,
+    pub offset: i64,
+}
+
+impl ParameterPoint {
+    pub fn new(
+        temperature: Parameter,
+        humidity: Parameter,
+        continentalness: Parameter,
+        erosion: Parameter,
+        depth: Parameter,
+        weirdness: Parameter,
+        offset: f32,
+    ) -> Self {
+        ParameterPoint {
+            temperature,
+            humidity,
+            continentalness,
+            erosion,
+            depth,
+            weirdness,
+            offset: quantize(offset),
+        }
+    }
+
+    /// The squared distance of `target` to this point, lower is a better fit.
+    pub fn fitness(&self, target: &TargetPoint) -> i64 {
+        square(self.temperature.distance(target.temperature))
+            + square(self.humidity.distance(target.humidity))
+            + square(self.continentalness.distance(target.continentalness))
+            + square(self.erosion.distance(target.erosion))
+            + square(self.depth.distance(target.depth))
+            + square(self.weirdness.distance(target.weirdness))
+            + square(self.offset)
+    }
+}
+
+fn square(v: i64) -> i64 {
+    v * v
+}
+
+/// Values tagged with the climate they are picked for.
+#[derive(Clone, Debug)]
+pub struct ParameterList<T> {
+    entries: Vec<(ParameterPoint, T)>,
+}
+
+impl<T: Copy> ParameterList<T> {
+    /// Panics if `entries` is empty.
+    pub fn new(entries: Vec<(ParameterPoint, T)>) -> Self {
+        assert!(!entries.is_empty(), "a parameter list needs at least one value");
+
+        ParameterList { entries }
+    }
+
+    pub fn entries(&self) -> &[(ParameterPoint, T)] {
+        &self.entries
+    }
+
+    /// Returns the value of the point closest to `target`. Ties go to the value listed first.
+    pub fn find_value(&self, target: &TargetPoint) -> T {
+        let mut best = &self.entries[0];
+        let mut best_fitness = i64::MAX;
+
+        for entry in &self.entries {
+            let fitness = entry.0.fitness(target);
+
+            if fitness < best_fitness {
+                best = entry;
+                best_fitness = fitness;
+
+                if fitness == 0 {
+                    break;
+                }
+            }
+        }
+
+        best.1
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{quantize, Parameter, ParameterList, ParameterPoint, TargetPoint};
+
+    #[test]
+    fn test_parameter() {
+        assert_eq!(quantize(-0.11), -1100);
+        assert_eq!(quantize(0.55), 5500);
+
+        let p = Parameter::span(-0.2, 0.3);
+        assert_eq!(p.distance(quantize(0.0)), 0);
+        assert_eq!(p.distance(quantize(0.5)), 2000);
+        assert_eq!(p.distance(quantize(-1.0)), 8000);
+        assert_eq!(p.union(Parameter::point(1.0)), Parameter { min: -2000, max: 10000 });
+    }
+
+    #[test]
+    fn test_find_value() {
+        let full = Parameter::span(-1.0, 1.0);
+        let point = |t: Parameter, offset| ParameterPoint::new(t, full, full, full, full, full, offset);
+
+        let list = ParameterList::new(vec![
+            (point(Parameter::span(-1.0, 0.0), 0.0), 'a'),
+            (point(Parameter::span(0.0, 0.5), 0.0), 'b'),
+            (point(Parameter::span(0.0, 1.0), 0.1), 'c'),
+        ]);
+
+        let target = |t| TargetPoint::new(t, 0.0, 0.0, 0.0, 0.0, 0.0);
+
+        assert_eq!(list.find_value(&target(-0.5)), 'a');
+        // shared border, the first entry wins
+        assert_eq!(list.find_value(&target(0.0)), 'a');
+        assert_eq!(list.find_value(&target(0.3)), 'b');
+        // 'c' contains the target, but is penalized by its offset
+        assert_eq!(list.find_value(&target(0.55)), 'b');
+        assert_eq!(list.find_value(&target(0.8)), 'c');
+    }
+}
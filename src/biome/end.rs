@@ -0,0 +1,149 @@
+//! The biomes of the end, split by the height of the outer islands since 1.13.
+//!
+//! End biomes are the same across a whole chunk: the main island within 1024 blocks of the origin,
+//! then highlands, midlands, barrens and small islands from the highest islands down to the void.
+
+use crate::rand::noise::SimplexNoise;
+use crate::rand::source::{LegacyRandomSource, RandomSource};
+use crate::util::{ChunkPoint, Version};
+
+use super::{Biome, BiomeSource};
+
+// the main island spans this many chunks around the origin
+const MAIN_ISLAND_CHUNKS: i64 = 64;
+
+// random calls made before the island noise is created
+const ISLAND_NOISE_SKIP: u32 = 17292;
+
+/// The biome source of the end.
+#[derive(Clone, Debug)]
+pub struct EndBiomeSource {
+    /// `None` before 1.13, when the whole end was a single biome.
+    islands: Option<SimplexNoise>,
+}
+
+impl EndBiomeSource {
+    pub fn new(seed: i64, version: Version) -> Self {
+        let islands = (version >= Version::V1_13).then(|| {
+            let mut rnd = LegacyRandomSource::new(seed);
+            rnd.consume_count(ISLAND_NOISE_SKIP);
+
+            SimplexNoise::new(&mut rnd)
+        });
+
+        EndBiomeSource { islands }
+    }
+
+    /// Returns the biome of the chunk `c`.
+    pub fn biome_at_chunk(&self, c: ChunkPoint) -> Biome {
+        let noise = match &self.islands {
+            Some(noise) => noise,
+            None => return Biome::TheEnd,
+        };
+
+        let (x, z) = (c.x as i64, c.z as i64);
+        if x * x + z * z <= MAIN_ISLAND_CHUNKS * MAIN_ISLAND_CHUNKS {
+            return Biome::TheEnd;
+        }
+
+        let height = island_height(noise, c.x * 2 + 1, c.z * 2 + 1);
+
+        if height > 40.0 {
+            Biome::EndHighlands
+        } else if height >= 0.0 {
+            Biome::EndMidlands
+        } else if height < -20.0 {
+            Biome::SmallEndIslands
+        } else {
+            Biome::EndBarrens
+        }
+    }
+
+    /// The height of the end terrain at the center of chunk `c`, between `-100` and `80`. Islands
+    /// reach above `0`. Always `0` before 1.13, where this was not computed by the biome source.
+    /// Like in the game, it may be `NaN` beyond chunk 16383 on both axes, which counts as barrens.
+    pub fn island_height(&self, c: ChunkPoint) -> f32 {
+        self.islands
+            .as_ref()
+            .map_or(0.0, |noise| island_height(noise, c.x * 2 + 1, c.z * 2 + 1))
+    }
+}
+
+impl BiomeSource for EndBiomeSource {
+    fn biome_at_quart(&self, qx: i32, _qy: i32, qz: i32) -> Biome {
+        self.biome_at_chunk(ChunkPoint { x: qx >> 2, z: qz >> 2 })
+    }
+}
+
+/// `EndIslandDensityFunction.getHeightValue`, `x` and `z` are in units of 8 blocks.
+/// The squared distance to the origin wraps like the game's `int`s.
+fn island_height(noise: &SimplexNoise, x: i32, z: i32) -> f32 {
+    let (i, j) = (x / 2, z / 2);
+    let (k, l) = (x % 2, z % 2);
+
+    let dist = (x.wrapping_mul(x).wrapping_add(z.wrapping_mul(z)) as f32).sqrt();
+    let mut height = (100.0 - dist * 8.0).clamp(-100.0, 80.0);
+
+    for m in -12..=12 {
+        for n in -12..=12 {
+            let (o, p) = ((i + m) as i64, (j + n) as i64);
+
+            if o * o + p * p > 4096 && noise.value_2d(o as f64, p as f64) < -0.9f32 as f64 {
+                let size = ((o as f32).abs() * 3439.0 + (p as f32).abs() * 147.0) % 13.0 + 9.0;
+                let (h, q) = ((k - m * 2) as f32, (l - n * 2) as f32);
+
+                let island = (100.0 - (h * h + q * q).sqrt() * size).clamp(-100.0, 80.0);
+
+                // `Math.max` keeps a `NaN` height, unlike `f32::max`
+                if island > height {
+                    height = island;
+                }
+            }
+        }
+    }
+
+    height
+}
+
+#[cfg(test)]
+mod test {
+    use crate::biome::{Biome, BiomeSource};
+    use crate::util::{ChunkPoint, Point3D, Version};
+
+    use super::EndBiomeSource;
+
+    #[test]
+    fn test_end_biomes() {
+        let source = EndBiomeSource::new(123, Version::V1_20);
+        let chunk = |x, z| ChunkPoint { x, z };
+
+        // pinned from this implementation
+        let expected = [
+            (100, 0, 74.54416, Biome::EndHighlands),
+            (-200, 37, 80.0, Biome::EndHighlands),
+            (70, -30, 30.02858, Biome::EndMidlands),
+            (235, 100, -7.0794067, Biome::EndBarrens),
+            (500, 500, -30.384048, Biome::SmallEndIslands),
+            (1234, -4321, -21.498962, Biome::SmallEndIslands),
+        ];
+
+        for &(x, z, height, biome) in &expected {
+            assert_eq!(source.island_height(chunk(x, z)), height);
+            assert_eq!(source.biome_at_chunk(chunk(x, z)), biome);
+        }
+
+        assert_eq!(source.biome_at_chunk(chunk(40, -40)), Biome::TheEnd);
+
+        // the whole chunk shares its biome
+        assert_eq!(source.biome_at(Point3D { x: 1600, y: 60, z: 15 }), Biome::EndHighlands);
+        assert_eq!(source.biome_at(Point3D { x: 1615, y: 60, z: 0 }), Biome::EndHighlands);
+
+        // the distance to the origin overflows and the height is NaN, which the game treats as barrens
+        assert!(source.island_height(chunk(16384, 16384)).is_nan());
+        assert_eq!(source.biome_at_chunk(chunk(16384, 16384)), Biome::EndBarrens);
+        assert_eq!(source.biome_at_chunk(chunk(-16384, 16384)), Biome::EndBarrens);
+
+        let old = EndBiomeSource::new(123, Version::V1_12);
+        assert_eq!(old.biome_at_chunk(chunk(100, 0)), Biome::TheEnd);
+    }
+}
@@ -0,0 +1,167 @@
+//! Grids of biomes over an area of a world, and rendering them as images.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use ndarray::Array2;
+
+use crate::util::{BlockPoint, Point3D};
+
+use super::{Biome, BiomeSource};
+
+/// The biomes of a horizontal area, sampled every `scale` blocks on a single `y` level.
+#[derive(Clone, Debug)]
+pub struct BiomeMap {
+    /// The north west corner of the map, the block sampled for the first cell.
+    pub origin: BlockPoint,
+
+    /// The number of blocks along each side of a cell.
+    pub scale: u32,
+
+    /// The biome of every cell, indexed by `[[x, z]]`.
+    pub biomes: Array2<Biome>,
+}
+
+impl BiomeMap {
+    /// Samples the biomes of the area outlined by `start` and `end`, at the north west corner of
+    /// every `scale` by `scale` cell. Both points must be on the same `y` level.
+    ///
+    /// A `scale` of 4 samples every quart, smaller scales repeat the same biome over several cells.
+    pub fn new<S: BiomeSource>(source: &S, start: BlockPoint, end: BlockPoint, scale: u32) -> Self {
+        assert!(start.x <= end.x);
+        assert!(start.z <= end.z);
+        assert_eq!(start.y, end.y);
+        assert!(scale > 0);
+
+        let s = scale as i64;
+        let width = ((end.x as i64 - start.x as i64) / s + 1) as usize;
+        let depth = ((end.z as i64 - start.z as i64) / s + 1) as usize;
+
+        let biomes = Array2::from_shape_fn((width, depth), |(i, j)| {
+            source.biome_at(Point3D {
+                x: start.x + (i as i64 * s) as i32,
+                y: start.y,
+                z: start.z + (j as i64 * s) as i32,
+            })
+        });
+
+        BiomeMap {
+            origin: start,
+            scale,
+            biomes,
+        }
+    }
+
+    /// The number of cells along the `x` axis.
+    pub fn width(&self) -> usize {
+        self.biomes.dim().0
+    }
+
+    /// The number of cells along the `z` axis.
+    pub fn depth(&self) -> usize {
+        self.biomes.dim().1
+    }
+
+    /// The block sampled for the cell `[[i, j]]`.
+    pub fn block_at(&self, i: usize, j: usize) -> BlockPoint {
+        Point3D {
+            x: self.origin.x + (i as i64 * self.scale as i64) as i32,
+            y: self.origin.y,
+            z: self.origin.z + (j as i64 * self.scale as i64) as i32,
+        }
+    }
+
+    /// Returns the biome of the cell holding the block `p`, `None` if it is outside the map.
+    /// Ignores the `y` coordinate.
+    pub fn get(&self, p: BlockPoint) -> Option<Biome> {
+        let i = (p.x as i64 - self.origin.x as i64).div_euclid(self.scale as i64);
+        let j = (p.z as i64 - self.origin.z as i64).div_euclid(self.scale as i64);
+
+        if i < 0 || j < 0 {
+            return None;
+        }
+
+        self.biomes.get([i as usize, j as usize]).copied()
+    }
+
+    /// The colours of the map, one RGB pixel per cell. Rows go from north to south, `x` grows to
+    /// the right.
+    pub fn to_rgb(&self) -> Vec<u8> {
+        let mut pixels = Vec::with_capacity(self.width() * self.depth() * 3);
+
+        for j in 0..self.depth() {
+            for i in 0..self.width() {
+                pixels.extend_from_slice(&self.biomes[[i, j]].color());
+            }
+        }
+
+        pixels
+    }
+
+    /// Encodes the map as a PNG image with one pixel per cell, see `to_rgb`.
+    pub fn write_png<W: Write>(&self, w: W) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(w, self.width() as u32, self.depth() as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.to_rgb())?;
+        writer.finish()?;
+
+        Ok(())
+    }
+
+    /// Writes the map as a PNG image to the file at `path`, see `write_png`.
+    pub fn save_png<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.write_png(BufWriter::new(File::create(path)?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::biome::{Biome, BiomeSource};
+    use crate::util::Point3D;
+
+    use super::BiomeMap;
+
+    // deserts east of x = 0, plains to the west
+    struct Halves;
+
+    impl BiomeSource for Halves {
+        fn biome_at_quart(&self, qx: i32, _qy: i32, _qz: i32) -> Biome {
+            if qx >= 0 {
+                Biome::Desert
+            } else {
+                Biome::Plains
+            }
+        }
+    }
+
+    #[test]
+    fn test_biome_map() {
+        let start = Point3D { x: -16, y: 64, z: -8 };
+        let end = Point3D { x: 15, y: 64, z: 7 };
+        let map = BiomeMap::new(&Halves, start, end, 4);
+
+        assert_eq!((map.width(), map.depth()), (8, 4));
+        assert_eq!(map.get(Point3D { x: -1, y: 0, z: 0 }), Some(Biome::Plains));
+        assert_eq!(map.get(Point3D { x: 0, y: 0, z: 7 }), Some(Biome::Desert));
+        assert_eq!(map.get(Point3D { x: -17, y: 0, z: 0 }), None);
+        assert_eq!(map.get(Point3D { x: 0, y: 0, z: 8 }), None);
+        assert_eq!(map.block_at(4, 3), Point3D { x: 0, y: 64, z: 4 });
+
+        let mut png = Vec::new();
+        map.write_png(&mut png).unwrap();
+
+        let decoder = png::Decoder::new(png.as_slice());
+        let mut reader = decoder.read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+
+        assert_eq!((info.width, info.height), (8, 4));
+        assert_eq!(pixels, map.to_rgb());
+        assert_eq!(pixels[..3], Biome::Plains.color());
+        assert_eq!(pixels[12..15], Biome::Desert.color());
+    }
+}
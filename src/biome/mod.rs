@@ -0,0 +1,277 @@
+//! This module predicts the biomes of a world from its seed.
+//!
+//! Overworld biomes are only supported for the multi noise generation of 1.18 onwards. Biomes are
+//! computed at the resolution the game stores them at, one biome per 4x4x4 cell of blocks (a "quart").
+
+mod climate;
+pub mod end;
+pub mod map;
+pub mod nether;
+pub mod overworld;
+pub mod spawn;
+mod spline;
+
+pub use climate::{quantize, Parameter, ParameterList, ParameterPoint, TargetPoint};
+pub use end::EndBiomeSource;
+pub use map::BiomeMap;
+pub use nether::NetherBiomeSource;
+pub use overworld::OverworldBiomeSource;
+pub use spawn::WorldSpawn;
+
+use std::fmt::Display;
+
+use crate::util::BlockPoint;
+
+/// Every biome generated by the biome sources of this module.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub enum Biome {
+    // overworld
+    Plains,
+    SunflowerPlains,
+    SnowyPlains,
+    IceSpikes,
+    Desert,
+    Swamp,
+    MangroveSwamp,
+    Forest,
+    FlowerForest,
+    BirchForest,
+    DarkForest,
+    OldGrowthBirchForest,
+    OldGrowthPineTaiga,
+    OldGrowthSpruceTaiga,
+    Taiga,
+    SnowyTaiga,
+    Savanna,
+    SavannaPlateau,
+    WindsweptHills,
+    WindsweptGravellyHills,
+    WindsweptForest,
+    WindsweptSavanna,
+    Jungle,
+    SparseJungle,
+    BambooJungle,
+    Badlands,
+    ErodedBadlands,
+    WoodedBadlands,
+    Meadow,
+    CherryGrove,
+    Grove,
+    SnowySlopes,
+    FrozenPeaks,
+    JaggedPeaks,
+    StonyPeaks,
+    River,
+    FrozenRiver,
+    Beach,
+    SnowyBeach,
+    StonyShore,
+    WarmOcean,
+    LukewarmOcean,
+    DeepLukewarmOcean,
+    Ocean,
+    DeepOcean,
+    ColdOcean,
+    DeepColdOcean,
+    FrozenOcean,
+    DeepFrozenOcean,
+    MushroomFields,
+    DripstoneCaves,
+    LushCaves,
+    DeepDark,
+    // nether
+    NetherWastes,
+    SoulSandValley,
+    CrimsonForest,
+    WarpedForest,
+    BasaltDeltas,
+    // end
+    TheEnd,
+    EndHighlands,
+    EndMidlands,
+    SmallEndIslands,
+    EndBarrens,
+}
+
+impl Biome {
+    /// The id of the biome, without the `minecraft:` namespace.
+    pub fn name(&self) -> &'static str {
+        use Biome::*;
+
+        match self {
+            Plains => "plains",
+            SunflowerPlains => "sunflower_plains",
+            SnowyPlains => "snowy_plains",
+            IceSpikes => "ice_spikes",
+            Desert => "desert",
+            Swamp => "swamp",
+            MangroveSwamp => "mangrove_swamp",
+            Forest => "forest",
+            FlowerForest => "flower_forest",
+            BirchForest => "birch_forest",
+            DarkForest => "dark_forest",
+            OldGrowthBirchForest => "old_growth_birch_forest",
+            OldGrowthPineTaiga => "old_growth_pine_taiga",
+            OldGrowthSpruceTaiga => "old_growth_spruce_taiga",
+            Taiga => "taiga",
+            SnowyTaiga => "snowy_taiga",
+            Savanna => "savanna",
+            SavannaPlateau => "savanna_plateau",
+            WindsweptHills => "windswept_hills",
+            WindsweptGravellyHills => "windswept_gravelly_hills",
+            WindsweptForest => "windswept_forest",
+            WindsweptSavanna => "windswept_savanna",
+            Jungle => "jungle",
+            SparseJungle => "sparse_jungle",
+            BambooJungle => "bamboo_jungle",
+            Badlands => "badlands",
+            ErodedBadlands => "eroded_badlands",
+            WoodedBadlands => "wooded_badlands",
+            Meadow => "meadow",
+            CherryGrove => "cherry_grove",
+            Grove => "grove",
+            SnowySlopes => "snowy_slopes",
+            FrozenPeaks => "frozen_peaks",
+            JaggedPeaks => "jagged_peaks",
+            StonyPeaks => "stony_peaks",
+            River => "river",
+            FrozenRiver => "frozen_river",
+            Beach => "beach",
+            SnowyBeach => "snowy_beach",
+            StonyShore => "stony_shore",
+            WarmOcean => "warm_ocean",
+            LukewarmOcean => "lukewarm_ocean",
+            DeepLukewarmOcean => "deep_lukewarm_ocean",
+            Ocean => "ocean",
+            DeepOcean => "deep_ocean",
+            ColdOcean => "cold_ocean",
+            DeepColdOcean => "deep_cold_ocean",
+            FrozenOcean => "frozen_ocean",
+            DeepFrozenOcean => "deep_frozen_ocean",
+            MushroomFields => "mushroom_fields",
+            DripstoneCaves => "dripstone_caves",
+            LushCaves => "lush_caves",
+            DeepDark => "deep_dark",
+            NetherWastes => "nether_wastes",
+            SoulSandValley => "soul_sand_valley",
+            CrimsonForest => "crimson_forest",
+            WarpedForest => "warped_forest",
+            BasaltDeltas => "basalt_deltas",
+            TheEnd => "the_end",
+            EndHighlands => "end_highlands",
+            EndMidlands => "end_midlands",
+            SmallEndIslands => "small_end_islands",
+            EndBarrens => "end_barrens",
+        }
+    }
+
+    /// The RGB colour biome maps commonly draw the biome with, as used by Amidst and cubiomes.
+    pub fn color(&self) -> [u8; 3] {
+        use Biome::*;
+
+        match self {
+            Plains => [141, 179, 96],
+            SunflowerPlains => [181, 219, 136],
+            SnowyPlains => [255, 255, 255],
+            IceSpikes => [180, 220, 220],
+            Desert => [250, 148, 24],
+            Swamp => [7, 249, 178],
+            MangroveSwamp => [44, 204, 142],
+            Forest => [5, 102, 33],
+            FlowerForest => [45, 142, 73],
+            BirchForest => [48, 116, 68],
+            DarkForest => [64, 81, 26],
+            OldGrowthBirchForest => [88, 156, 108],
+            OldGrowthPineTaiga => [89, 102, 81],
+            OldGrowthSpruceTaiga => [129, 142, 121],
+            Taiga => [11, 102, 89],
+            SnowyTaiga => [49, 85, 74],
+            Savanna => [189, 178, 95],
+            SavannaPlateau => [167, 157, 100],
+            WindsweptHills => [96, 96, 96],
+            WindsweptGravellyHills => [136, 136, 136],
+            WindsweptForest => [80, 112, 80],
+            WindsweptSavanna => [229, 218, 135],
+            Jungle => [83, 123, 9],
+            SparseJungle => [98, 139, 23],
+            BambooJungle => [118, 142, 20],
+            Badlands => [217, 69, 21],
+            ErodedBadlands => [255, 109, 61],
+            WoodedBadlands => [176, 151, 101],
+            Meadow => [96, 164, 69],
+            CherryGrove => [255, 145, 200],
+            Grove => [71, 114, 108],
+            SnowySlopes => [196, 196, 196],
+            FrozenPeaks => [176, 179, 206],
+            JaggedPeaks => [220, 220, 200],
+            StonyPeaks => [123, 143, 116],
+            River => [0, 0, 255],
+            FrozenRiver => [160, 160, 255],
+            Beach => [250, 222, 85],
+            SnowyBeach => [250, 240, 192],
+            StonyShore => [162, 162, 132],
+            WarmOcean => [0, 0, 172],
+            LukewarmOcean => [0, 0, 144],
+            DeepLukewarmOcean => [0, 0, 64],
+            Ocean => [0, 0, 112],
+            DeepOcean => [0, 0, 48],
+            ColdOcean => [32, 32, 112],
+            DeepColdOcean => [32, 32, 56],
+            FrozenOcean => [112, 112, 214],
+            DeepFrozenOcean => [64, 64, 144],
+            MushroomFields => [255, 0, 255],
+            DripstoneCaves => [78, 48, 18],
+            LushCaves => [40, 60, 0],
+            DeepDark => [3, 31, 41],
+            NetherWastes => [191, 59, 59],
+            SoulSandValley => [82, 41, 33],
+            CrimsonForest => [221, 8, 8],
+            WarpedForest => [73, 144, 123],
+            BasaltDeltas => [104, 95, 112],
+            TheEnd => [128, 128, 255],
+            EndHighlands => [181, 181, 54],
+            EndMidlands => [201, 201, 89],
+            SmallEndIslands => [75, 75, 171],
+            EndBarrens => [112, 112, 204],
+        }
+    }
+
+    pub fn is_ocean(&self) -> bool {
+        use Biome::*;
+
+        matches!(
+            self,
+            WarmOcean
+                | LukewarmOcean
+                | DeepLukewarmOcean
+                | Ocean
+                | DeepOcean
+                | ColdOcean
+                | DeepColdOcean
+                | FrozenOcean
+                | DeepFrozenOcean
+        )
+    }
+}
+
+impl Display for Biome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "minecraft:{}", self.name())
+    }
+}
+
+/// Converts a block coordinate to the coordinate of the quart holding it.
+pub fn quart(block: i32) -> i32 {
+    block >> 2
+}
+
+/// Anything which can tell the biome at a position of a world.
+pub trait BiomeSource {
+    /// Returns the biome of the quart (`qx`, `qy`, `qz`).
+    fn biome_at_quart(&self, qx: i32, qy: i32, qz: i32) -> Biome;
+
+    /// Returns the biome of the quart holding the block `p`.
+    fn biome_at(&self, p: BlockPoint) -> Biome {
+        self.biome_at_quart(quart(p.x), quart(p.y), quart(p.z))
+    }
+}
@@ -0,0 +1,153 @@
+//! The biomes of the nether, picked from five climate presets since 1.16.
+//!
+//! Only temperature and humidity vary across the nether. Both noises are seeded the way they were
+//! before 1.18, which the game kept for compatibility. Biomes don't change with height.
+
+use crate::rand::noise::{NoiseParameters, NormalNoise};
+use crate::rand::source::LegacyRandomSource;
+use crate::util::{Point3D, Version};
+
+use super::{Biome, BiomeSource, Parameter, ParameterList, ParameterPoint, TargetPoint};
+
+/// The climate of every nether biome: temperature, humidity and offset.
+const PRESETS: [(f32, f32, f32, Biome); 5] = [
+    (0.0, 0.0, 0.0, Biome::NetherWastes),
+    (0.0, -0.5, 0.0, Biome::SoulSandValley),
+    (0.4, 0.0, 0.0, Biome::CrimsonForest),
+    (0.0, 0.5, 0.375, Biome::WarpedForest),
+    (-0.5, 0.0, 0.175, Biome::BasaltDeltas),
+];
+
+/// The biome source of the nether.
+#[derive(Clone, Debug)]
+pub struct NetherBiomeSource {
+    version: Version,
+    temperature: NormalNoise,
+    humidity: NormalNoise,
+
+    /// Before 1.18, climates are compared unquantized and include two more noises. Those are the
+    /// same for every preset but still shift the rounding.
+    altitude: NormalNoise,
+    weirdness: NormalNoise,
+
+    parameters: ParameterList<Biome>,
+}
+
+impl NetherBiomeSource {
+    pub fn new(seed: i64, version: Version) -> Self {
+        let noise = |salt: i64| {
+            let mut rnd = LegacyRandomSource::new(seed.wrapping_add(salt));
+            NormalNoise::legacy(&mut rnd, &NoiseParameters::new(-7, &[1.0, 1.0]))
+        };
+
+        NetherBiomeSource {
+            version,
+            temperature: noise(0),
+            humidity: noise(1),
+            altitude: noise(2),
+            weirdness: noise(3),
+            parameters: parameters(),
+        }
+    }
+
+    fn legacy_biome(&self, p: Point3D<f64>) -> Biome {
+        let t = self.temperature.sample(p) as f32;
+        let h = self.humidity.sample(p) as f32;
+        let a = self.altitude.sample(p) as f32;
+        let w = self.weirdness.sample(p) as f32;
+
+        let mut best = (f32::MAX, Biome::NetherWastes);
+        for &(pt, ph, offset, biome) in &PRESETS {
+            let fitness = (t - pt) * (t - pt) + (h - ph) * (h - ph) + a * a + w * w + offset * offset;
+
+            if fitness < best.0 {
+                best = (fitness, biome);
+            }
+        }
+
+        best.1
+    }
+}
+
+impl BiomeSource for NetherBiomeSource {
+    fn biome_at_quart(&self, qx: i32, _qy: i32, qz: i32) -> Biome {
+        // 0.25 times the block coordinate is the quart coordinate again
+        let p = Point3D {
+            x: qx as f64,
+            y: 0.0,
+            z: qz as f64,
+        };
+
+        if self.version < Version::V1_16 {
+            // a single biome before 1.16
+            Biome::NetherWastes
+        } else if self.version < Version::V1_18 {
+            self.legacy_biome(p)
+        } else {
+            let target = TargetPoint::new(
+                self.temperature.sample(p) as f32,
+                self.humidity.sample(p) as f32,
+                0.0,
+                0.0,
+                0.0,
+                0.0,
+            );
+
+            self.parameters.find_value(&target)
+        }
+    }
+}
+
+/// Returns the climate of every nether biome from 1.18 on.
+pub fn parameters() -> ParameterList<Biome> {
+    let zero = Parameter::point(0.0);
+
+    ParameterList::new(
+        PRESETS
+            .iter()
+            .map(|&(t, h, offset, biome)| {
+                let point =
+                    ParameterPoint::new(Parameter::point(t), Parameter::point(h), zero, zero, zero, zero, offset);
+
+                (point, biome)
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use crate::biome::{Biome, BiomeSource};
+    use crate::util::{Point3D, Version};
+
+    use super::NetherBiomeSource;
+
+    #[test]
+    fn test_nether_biomes() {
+        let modern = NetherBiomeSource::new(123, Version::V1_20);
+        let legacy = NetherBiomeSource::new(123, Version::V1_16);
+
+        // pinned from this implementation, on quart coordinates
+        let expected = [
+            (0, 0, Biome::NetherWastes),
+            (25, -40, Biome::NetherWastes),
+            (-100, 77, Biome::WarpedForest),
+            (300, 300, Biome::SoulSandValley),
+            (-512, 64, Biome::BasaltDeltas),
+            (1000, -2000, Biome::SoulSandValley),
+            (0, -200, Biome::CrimsonForest),
+        ];
+
+        for &(qx, qz, biome) in &expected {
+            assert_eq!(modern.biome_at_quart(qx, 8, qz), biome);
+            assert_eq!(legacy.biome_at_quart(qx, 8, qz), biome);
+        }
+
+        // biomes don't change with height
+        let p = Point3D { x: -400, y: 20, z: 310 };
+        assert_eq!(modern.biome_at(p), modern.biome_at(Point3D { y: 120, ..p }));
+
+        let old = NetherBiomeSource::new(123, Version::V1_14);
+        assert_eq!(old.biome_at_quart(-512, 8, 64), Biome::NetherWastes);
+    }
+}
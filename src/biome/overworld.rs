@@ -0,0 +1,968 @@
+//! The multi noise biome source of the overworld, as introduced in 1.18.
+//!
+//! see: `OverworldBiomeBuilder` and `NoiseRouterData` in the vanilla source
+
+use crate::rand::noise::{NoiseParameters, NormalNoise};
+use crate::rand::source::{PositionalRandomFactory, RandomSource, XoroshiroRandomSource};
+use crate::util::{Point3D, Version};
+
+use super::spline::{overworld_offset, Spline, SplineInput};
+use super::{Biome, BiomeSource, Parameter, ParameterList, ParameterPoint, TargetPoint};
+
+use Biome::*;
+
+// the terrain height spanned by the depth gradient
+const MIN_Y: f64 = -64.0;
+const MAX_Y: f64 = 320.0;
+
+// added to the offset spline, the sea level sits at an offset of about 0
+const OFFSET: f32 = -0.50375;
+
+/// Samples the continentalness of the overworld alone, the first part of a `ClimateSampler`. Way
+/// cheaper to build and sample than the whole climate, this is enough to tell oceans from land.
+#[derive(Clone, Debug)]
+pub struct ContinentalnessSampler {
+    seed: i64,
+    continentalness: NormalNoise,
+    shift: NormalNoise,
+}
+
+impl ContinentalnessSampler {
+    pub fn new(seed: i64) -> Self {
+        ContinentalnessSampler {
+            seed,
+            continentalness: noise(
+                seed,
+                "continentalness",
+                -9,
+                &[1.0, 1.0, 2.0, 2.0, 2.0, 1.0, 1.0, 1.0, 1.0],
+            ),
+            shift: noise(seed, "offset", -3, &[1.0, 1.0, 1.0, 0.0]),
+        }
+    }
+
+    /// The continentalness of the block column (`x`, `z`).
+    pub fn continentalness(&self, x: i32, z: i32) -> f64 {
+        self.shifted(&self.continentalness, x, z, self.shift(x, z))
+    }
+
+    /// Builds the remaining noises of the climate.
+    pub fn into_climate(self) -> ClimateSampler {
+        let seed = self.seed;
+
+        ClimateSampler {
+            temperature: noise(seed, "temperature", -10, &[1.5, 0.0, 1.0, 0.0, 0.0, 0.0]),
+            humidity: noise(seed, "vegetation", -8, &[1.0, 1.0, 0.0, 0.0, 0.0, 0.0]),
+            erosion: noise(seed, "erosion", -9, &[1.0, 1.0, 0.0, 1.0, 1.0]),
+            ridges: noise(seed, "ridge", -7, &[1.0, 2.0, 1.0, 0.0, 0.0, 0.0]),
+            continents: self,
+            offset: overworld_offset(),
+        }
+    }
+
+    fn shift(&self, x: i32, z: i32) -> (f64, f64) {
+        let (x, z) = (x as f64, z as f64);
+
+        (
+            self.shift.sample(Point3D {
+                x: x * 0.25,
+                y: 0.0,
+                z: z * 0.25,
+            }) * 4.0,
+            self.shift.sample(Point3D {
+                x: z * 0.25,
+                y: x * 0.25,
+                z: 0.0,
+            }) * 4.0,
+        )
+    }
+
+    fn shifted(&self, noise: &NormalNoise, x: i32, z: i32, (shift_x, shift_z): (f64, f64)) -> f64 {
+        noise.sample(Point3D {
+            x: x as f64 * 0.25 + shift_x,
+            y: 0.0,
+            z: z as f64 * 0.25 + shift_z,
+        })
+    }
+}
+
+/// The climate noise `name` of a `seed`, every noise has its own random.
+fn noise(seed: i64, name: &str, first_octave: i32, amplitudes: &[f64]) -> NormalNoise {
+    let factory = XoroshiroRandomSource::new(seed).fork_positional();
+    let mut rnd = factory.from_hash_of(&format!("minecraft:{}", name));
+
+    NormalNoise::new(&mut rnd, &NoiseParameters::new(first_octave, amplitudes))
+}
+
+/// Samples the climate of the overworld, as `Climate.Sampler` in the game.
+#[derive(Clone, Debug)]
+pub struct ClimateSampler {
+    temperature: NormalNoise,
+    humidity: NormalNoise,
+    erosion: NormalNoise,
+    ridges: NormalNoise,
+    continents: ContinentalnessSampler,
+    offset: Spline,
+}
+
+impl ClimateSampler {
+    pub fn new(seed: i64) -> Self {
+        ContinentalnessSampler::new(seed).into_climate()
+    }
+
+    /// Samples the climate of the quart (`qx`, `qy`, `qz`).
+    pub fn sample(&self, qx: i32, qy: i32, qz: i32) -> TargetPoint {
+        let (x, y, z) = (qx * 4, qy * 4, qz * 4);
+        let c = &self.continents;
+        let shift = c.shift(x, z);
+
+        let continentalness = c.shifted(&c.continentalness, x, z, shift);
+        let erosion = c.shifted(&self.erosion, x, z, shift);
+        let weirdness = c.shifted(&self.ridges, x, z, shift);
+
+        let offset = self.offset.apply(&SplineInput {
+            continentalness: continentalness as f32,
+            erosion: erosion as f32,
+            ridges_folded: ridges_folded(weirdness) as f32,
+        });
+        let depth = depth_gradient(y) + (OFFSET as f64 + offset as f64);
+
+        TargetPoint::new(
+            c.shifted(&self.temperature, x, z, shift) as f32,
+            c.shifted(&self.humidity, x, z, shift) as f32,
+            continentalness as f32,
+            erosion as f32,
+            depth as f32,
+            weirdness as f32,
+        )
+    }
+
+    /// The continentalness of the block column (`x`, `z`), see `ContinentalnessSampler`.
+    pub fn continentalness(&self, x: i32, z: i32) -> f64 {
+        self.continents.continentalness(x, z)
+    }
+
+    pub fn continents(&self) -> &ContinentalnessSampler {
+        &self.continents
+    }
+}
+
+fn ridges_folded(weirdness: f64) -> f64 {
+    -((weirdness.abs() - 0.6666666666666666).abs() - 0.3333333333333333) * 3.0
+}
+
+fn depth_gradient(y: i32) -> f64 {
+    let delta = (y as f64 - MIN_Y) / (MAX_Y - MIN_Y);
+
+    if delta < 0.0 {
+        1.5
+    } else if delta > 1.0 {
+        -1.5
+    } else {
+        1.5 + delta * (-1.5 - 1.5)
+    }
+}
+
+/// The biome source of the overworld from 1.18 on.
+#[derive(Clone, Debug)]
+pub struct OverworldBiomeSource {
+    sampler: ClimateSampler,
+    parameters: ParameterList<Biome>,
+}
+
+impl OverworldBiomeSource {
+    /// Panics if `version` predates 1.18.
+    pub fn new(seed: i64, version: Version) -> Self {
+        assert!(version >= Version::V1_18, "multi noise biomes need 1.18 or later");
+
+        OverworldBiomeSource {
+            sampler: ClimateSampler::new(seed),
+            parameters: parameters(version),
+        }
+    }
+
+    pub fn sampler(&self) -> &ClimateSampler {
+        &self.sampler
+    }
+}
+
+impl BiomeSource for OverworldBiomeSource {
+    fn biome_at_quart(&self, qx: i32, qy: i32, qz: i32) -> Biome {
+        self.parameters.find_value(&self.sampler.sample(qx, qy, qz))
+    }
+}
+
+/// Returns the climate of every overworld biome, in the order the game lists them.
+pub fn parameters(version: Version) -> ParameterList<Biome> {
+    let mut builder = Builder::new(version);
+
+    builder.add_off_coast();
+    builder.add_inland();
+    builder.add_underground();
+
+    ParameterList::new(builder.entries)
+}
+
+/// Returns the climates the world spawn is searched for: inland, at the surface and away from the
+/// rivers and peaks of weirdness `0`.
+pub fn spawn_target() -> [ParameterPoint; 2] {
+    let full = span(FULL_RANGE);
+    let inland = span_of(INLAND_CONTINENTALNESS, FULL_RANGE);
+    let surface = Parameter::point(0.0);
+
+    [
+        ParameterPoint::new(full, full, inland, full, surface, Parameter::span(-1.0, -0.16), 0.0),
+        ParameterPoint::new(full, full, inland, full, surface, Parameter::span(0.16, 1.0), 0.0),
+    ]
+}
+
+const FULL_RANGE: (f32, f32) = (-1.0, 1.0);
+
+const TEMPERATURES: [(f32, f32); 5] = [(-1.0, -0.45), (-0.45, -0.15), (-0.15, 0.2), (0.2, 0.55), (0.55, 1.0)];
+const HUMIDITIES: [(f32, f32); 5] = [(-1.0, -0.35), (-0.35, -0.1), (-0.1, 0.1), (0.1, 0.3), (0.3, 1.0)];
+const EROSIONS: [(f32, f32); 7] = [
+    (-1.0, -0.78),
+    (-0.78, -0.375),
+    (-0.375, -0.2225),
+    (-0.2225, 0.05),
+    (0.05, 0.45),
+    (0.45, 0.55),
+    (0.55, 1.0),
+];
+
+const MUSHROOM_FIELDS_CONTINENTALNESS: (f32, f32) = (-1.2, -1.05);
+const DEEP_OCEAN_CONTINENTALNESS: (f32, f32) = (-1.05, -0.455);
+const OCEAN_CONTINENTALNESS: (f32, f32) = (-0.455, -0.19);
+const COAST_CONTINENTALNESS: (f32, f32) = (-0.19, -0.11);
+const INLAND_CONTINENTALNESS: (f32, f32) = (-0.11, 0.55);
+const NEAR_INLAND_CONTINENTALNESS: (f32, f32) = (-0.11, 0.03);
+const MID_INLAND_CONTINENTALNESS: (f32, f32) = (0.03, 0.3);
+const FAR_INLAND_CONTINENTALNESS: (f32, f32) = (0.3, 1.0);
+
+const OCEANS: [[Biome; 5]; 2] = [
+    [DeepFrozenOcean, DeepColdOcean, DeepOcean, DeepLukewarmOcean, WarmOcean],
+    [FrozenOcean, ColdOcean, Ocean, LukewarmOcean, WarmOcean],
+];
+
+const MIDDLE_BIOMES: [[Biome; 5]; 5] = [
+    [SnowyPlains, SnowyPlains, SnowyPlains, SnowyTaiga, Taiga],
+    [Plains, Plains, Forest, Taiga, OldGrowthSpruceTaiga],
+    [FlowerForest, Plains, Forest, BirchForest, DarkForest],
+    [Savanna, Savanna, Forest, Jungle, Jungle],
+    [Desert, Desert, Desert, Desert, Desert],
+];
+
+const MIDDLE_BIOMES_VARIANT: [[Option<Biome>; 5]; 5] = [
+    [Some(IceSpikes), None, Some(SnowyTaiga), None, None],
+    [None, None, None, None, Some(OldGrowthPineTaiga)],
+    [Some(SunflowerPlains), None, None, Some(OldGrowthBirchForest), None],
+    [None, None, Some(Plains), Some(SparseJungle), Some(BambooJungle)],
+    [None, None, None, None, None],
+];
+
+const PLATEAU_BIOMES: [[Biome; 5]; 5] = [
+    [SnowyPlains, SnowyPlains, SnowyPlains, SnowyTaiga, SnowyTaiga],
+    [Meadow, Meadow, Forest, Taiga, OldGrowthSpruceTaiga],
+    [Meadow, Meadow, Meadow, Meadow, DarkForest],
+    [SavannaPlateau, SavannaPlateau, Forest, Forest, Jungle],
+    [Badlands, Badlands, Badlands, WoodedBadlands, WoodedBadlands],
+];
+
+const PLATEAU_BIOMES_VARIANT: [[Option<Biome>; 5]; 5] = [
+    [Some(IceSpikes), None, None, None, None],
+    [
+        Some(CherryGrove),
+        None,
+        Some(Meadow),
+        Some(Meadow),
+        Some(OldGrowthPineTaiga),
+    ],
+    [
+        Some(CherryGrove),
+        Some(CherryGrove),
+        Some(Forest),
+        Some(BirchForest),
+        None,
+    ],
+    [None, None, None, None, None],
+    [Some(ErodedBadlands), Some(ErodedBadlands), None, None, None],
+];
+
+const SHATTERED_BIOMES: [[Option<Biome>; 5]; 5] = [
+    [
+        Some(WindsweptGravellyHills),
+        Some(WindsweptGravellyHills),
+        Some(WindsweptHills),
+        Some(WindsweptForest),
+        Some(WindsweptForest),
+    ],
+    [
+        Some(WindsweptGravellyHills),
+        Some(WindsweptGravellyHills),
+        Some(WindsweptHills),
+        Some(WindsweptForest),
+        Some(WindsweptForest),
+    ],
+    [
+        Some(WindsweptHills),
+        Some(WindsweptHills),
+        Some(WindsweptHills),
+        Some(WindsweptForest),
+        Some(WindsweptForest),
+    ],
+    [None, None, None, None, None],
+    [None, None, None, None, None],
+];
+
+fn span(range: (f32, f32)) -> Parameter {
+    Parameter::span(range.0, range.1)
+}
+
+fn span_of(min: (f32, f32), max: (f32, f32)) -> Parameter {
+    span(min).union(span(max))
+}
+
+fn temperature(i: usize) -> Parameter {
+    span(TEMPERATURES[i])
+}
+
+fn humidity(j: usize) -> Parameter {
+    span(HUMIDITIES[j])
+}
+
+fn erosion(i: usize) -> Parameter {
+    span(EROSIONS[i])
+}
+
+fn erosion_span(min: usize, max: usize) -> Parameter {
+    span_of(EROSIONS[min], EROSIONS[max])
+}
+
+/// Port of `OverworldBiomeBuilder`. `i` indexes the temperatures and `j` the humidities.
+struct Builder {
+    version: Version,
+    entries: Vec<(ParameterPoint, Biome)>,
+}
+
+impl Builder {
+    fn new(version: Version) -> Self {
+        Builder {
+            version,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Replaces biomes the version doesn't know about.
+    fn biome(&self, biome: Biome) -> Biome {
+        match biome {
+            MangroveSwamp if self.version < Version::V1_19 => Swamp,
+            b => b,
+        }
+    }
+
+    fn add_off_coast(&mut self) {
+        let full = span(FULL_RANGE);
+
+        self.add_surface(
+            full,
+            full,
+            span(MUSHROOM_FIELDS_CONTINENTALNESS),
+            full,
+            full,
+            MushroomFields,
+        );
+
+        for (i, &t) in TEMPERATURES.iter().enumerate() {
+            self.add_surface(span(t), full, span(DEEP_OCEAN_CONTINENTALNESS), full, full, OCEANS[0][i]);
+            self.add_surface(span(t), full, span(OCEAN_CONTINENTALNESS), full, full, OCEANS[1][i]);
+        }
+    }
+
+    fn add_inland(&mut self) {
+        self.add_mid_slice(Parameter::span(-1.0, -0.93333334));
+        self.add_high_slice(Parameter::span(-0.93333334, -0.7666667));
+        self.add_peaks(Parameter::span(-0.7666667, -0.56666666));
+        self.add_high_slice(Parameter::span(-0.56666666, -0.4));
+        self.add_mid_slice(Parameter::span(-0.4, -0.26666668));
+        self.add_low_slice(Parameter::span(-0.26666668, -0.05));
+        self.add_valleys(Parameter::span(-0.05, 0.05));
+        self.add_low_slice(Parameter::span(0.05, 0.26666668));
+        self.add_mid_slice(Parameter::span(0.26666668, 0.4));
+        self.add_high_slice(Parameter::span(0.4, 0.56666666));
+        self.add_peaks(Parameter::span(0.56666666, 0.7666667));
+        self.add_high_slice(Parameter::span(0.7666667, 0.93333334));
+        self.add_mid_slice(Parameter::span(0.93333334, 1.0));
+    }
+
+    fn add_peaks(&mut self, w: Parameter) {
+        let coast_far = span_of(COAST_CONTINENTALNESS, FAR_INLAND_CONTINENTALNESS);
+        let coast_near = span_of(COAST_CONTINENTALNESS, NEAR_INLAND_CONTINENTALNESS);
+        let mid_far = span_of(MID_INLAND_CONTINENTALNESS, FAR_INLAND_CONTINENTALNESS);
+
+        for i in 0..TEMPERATURES.len() {
+            for j in 0..HUMIDITIES.len() {
+                let (t, h) = (temperature(i), humidity(j));
+
+                let middle = self.pick_middle(i, j, w);
+                let middle_or_badlands = self.pick_middle_or_badlands_if_hot(i, j, w);
+                let middle_or_slope = self.pick_middle_or_badlands_if_hot_or_slope_if_cold(i, j, w);
+                let plateau = self.pick_plateau(i, j, w);
+                let shattered = self.pick_shattered(i, j, w);
+                let windswept = self.maybe_pick_windswept_savanna(i, j, w, shattered);
+                let peak = self.pick_peak(i, j, w);
+
+                self.add_surface(t, h, coast_far, erosion(0), w, peak);
+                self.add_surface(t, h, coast_near, erosion(1), w, middle_or_slope);
+                self.add_surface(t, h, mid_far, erosion(1), w, peak);
+                self.add_surface(t, h, coast_near, erosion_span(2, 3), w, middle);
+                self.add_surface(t, h, mid_far, erosion(2), w, plateau);
+                self.add_surface(
+                    t,
+                    h,
+                    span(MID_INLAND_CONTINENTALNESS),
+                    erosion(3),
+                    w,
+                    middle_or_badlands,
+                );
+                self.add_surface(t, h, span(FAR_INLAND_CONTINENTALNESS), erosion(3), w, plateau);
+                self.add_surface(t, h, coast_far, erosion(4), w, middle);
+                self.add_surface(t, h, coast_near, erosion(5), w, windswept);
+                self.add_surface(t, h, mid_far, erosion(5), w, shattered);
+                self.add_surface(t, h, coast_far, erosion(6), w, middle);
+            }
+        }
+    }
+
+    fn add_high_slice(&mut self, w: Parameter) {
+        let coast_far = span_of(COAST_CONTINENTALNESS, FAR_INLAND_CONTINENTALNESS);
+        let coast_near = span_of(COAST_CONTINENTALNESS, NEAR_INLAND_CONTINENTALNESS);
+        let mid_far = span_of(MID_INLAND_CONTINENTALNESS, FAR_INLAND_CONTINENTALNESS);
+
+        for i in 0..TEMPERATURES.len() {
+            for j in 0..HUMIDITIES.len() {
+                let (t, h) = (temperature(i), humidity(j));
+
+                let middle = self.pick_middle(i, j, w);
+                let middle_or_badlands = self.pick_middle_or_badlands_if_hot(i, j, w);
+                let middle_or_slope = self.pick_middle_or_badlands_if_hot_or_slope_if_cold(i, j, w);
+                let plateau = self.pick_plateau(i, j, w);
+                let shattered = self.pick_shattered(i, j, w);
+                let windswept = self.maybe_pick_windswept_savanna(i, j, w, middle);
+                let slope = self.pick_slope(i, j, w);
+                let peak = self.pick_peak(i, j, w);
+
+                self.add_surface(t, h, span(COAST_CONTINENTALNESS), erosion_span(0, 1), w, middle);
+                self.add_surface(t, h, span(NEAR_INLAND_CONTINENTALNESS), erosion(0), w, slope);
+                self.add_surface(t, h, mid_far, erosion(0), w, peak);
+                self.add_surface(t, h, span(NEAR_INLAND_CONTINENTALNESS), erosion(1), w, middle_or_slope);
+                self.add_surface(t, h, mid_far, erosion(1), w, slope);
+                self.add_surface(t, h, coast_near, erosion_span(2, 3), w, middle);
+                self.add_surface(t, h, mid_far, erosion(2), w, plateau);
+                self.add_surface(
+                    t,
+                    h,
+                    span(MID_INLAND_CONTINENTALNESS),
+                    erosion(3),
+                    w,
+                    middle_or_badlands,
+                );
+                self.add_surface(t, h, span(FAR_INLAND_CONTINENTALNESS), erosion(3), w, plateau);
+                self.add_surface(t, h, coast_far, erosion(4), w, middle);
+                self.add_surface(t, h, coast_near, erosion(5), w, windswept);
+                self.add_surface(t, h, mid_far, erosion(5), w, shattered);
+                self.add_surface(t, h, coast_far, erosion(6), w, middle);
+            }
+        }
+    }
+
+    fn add_mid_slice(&mut self, w: Parameter) {
+        let full = span(FULL_RANGE);
+        let coast = span(COAST_CONTINENTALNESS);
+        let near = span(NEAR_INLAND_CONTINENTALNESS);
+        let mid = span(MID_INLAND_CONTINENTALNESS);
+        let far = span(FAR_INLAND_CONTINENTALNESS);
+        let coast_near = span_of(COAST_CONTINENTALNESS, NEAR_INLAND_CONTINENTALNESS);
+        let coast_far = span_of(COAST_CONTINENTALNESS, FAR_INLAND_CONTINENTALNESS);
+        let near_mid = span_of(NEAR_INLAND_CONTINENTALNESS, MID_INLAND_CONTINENTALNESS);
+        let near_far = span_of(NEAR_INLAND_CONTINENTALNESS, FAR_INLAND_CONTINENTALNESS);
+        let mid_far = span_of(MID_INLAND_CONTINENTALNESS, FAR_INLAND_CONTINENTALNESS);
+
+        self.add_surface(full, full, coast, erosion_span(0, 2), w, StonyShore);
+        self.add_swamps(near_far, w);
+
+        for i in 0..TEMPERATURES.len() {
+            for j in 0..HUMIDITIES.len() {
+                let (t, h) = (temperature(i), humidity(j));
+
+                let middle = self.pick_middle(i, j, w);
+                let middle_or_badlands = self.pick_middle_or_badlands_if_hot(i, j, w);
+                let middle_or_slope = self.pick_middle_or_badlands_if_hot_or_slope_if_cold(i, j, w);
+                let shattered = self.pick_shattered(i, j, w);
+                let plateau = self.pick_plateau(i, j, w);
+                let beach = self.pick_beach(i, j);
+                let windswept = self.maybe_pick_windswept_savanna(i, j, w, middle);
+                let shattered_coast = self.pick_shattered_coast(i, j, w);
+                let slope = self.pick_slope(i, j, w);
+
+                self.add_surface(t, h, near_far, erosion(0), w, slope);
+                self.add_surface(t, h, near_mid, erosion(1), w, middle_or_slope);
+                self.add_surface(t, h, far, erosion(1), w, if i == 0 { slope } else { plateau });
+                self.add_surface(t, h, near, erosion(2), w, middle);
+                self.add_surface(t, h, mid, erosion(2), w, middle_or_badlands);
+                self.add_surface(t, h, far, erosion(2), w, plateau);
+                self.add_surface(t, h, coast_near, erosion(3), w, middle);
+                self.add_surface(t, h, mid_far, erosion(3), w, middle_or_badlands);
+
+                if w.max < 0 {
+                    self.add_surface(t, h, coast, erosion(4), w, beach);
+                    self.add_surface(t, h, near_far, erosion(4), w, middle);
+                } else {
+                    self.add_surface(t, h, coast_far, erosion(4), w, middle);
+                }
+
+                self.add_surface(t, h, coast, erosion(5), w, shattered_coast);
+                self.add_surface(t, h, near, erosion(5), w, windswept);
+                self.add_surface(t, h, mid_far, erosion(5), w, shattered);
+
+                if w.max < 0 {
+                    self.add_surface(t, h, coast, erosion(6), w, beach);
+                } else {
+                    self.add_surface(t, h, coast, erosion(6), w, middle);
+                }
+
+                if i == 0 {
+                    self.add_surface(t, h, near_far, erosion(6), w, middle);
+                }
+            }
+        }
+    }
+
+    fn add_low_slice(&mut self, w: Parameter) {
+        let full = span(FULL_RANGE);
+        let coast = span(COAST_CONTINENTALNESS);
+        let near = span(NEAR_INLAND_CONTINENTALNESS);
+        let near_far = span_of(NEAR_INLAND_CONTINENTALNESS, FAR_INLAND_CONTINENTALNESS);
+        let mid_far = span_of(MID_INLAND_CONTINENTALNESS, FAR_INLAND_CONTINENTALNESS);
+
+        self.add_surface(full, full, coast, erosion_span(0, 2), w, StonyShore);
+        self.add_swamps(near_far, w);
+
+        for i in 0..TEMPERATURES.len() {
+            for j in 0..HUMIDITIES.len() {
+                let (t, h) = (temperature(i), humidity(j));
+
+                let middle = self.pick_middle(i, j, w);
+                let middle_or_badlands = self.pick_middle_or_badlands_if_hot(i, j, w);
+                let middle_or_slope = self.pick_middle_or_badlands_if_hot_or_slope_if_cold(i, j, w);
+                let beach = self.pick_beach(i, j);
+                let windswept = self.maybe_pick_windswept_savanna(i, j, w, middle);
+                let shattered_coast = self.pick_shattered_coast(i, j, w);
+
+                self.add_surface(t, h, near, erosion_span(0, 1), w, middle_or_badlands);
+                self.add_surface(t, h, mid_far, erosion_span(0, 1), w, middle_or_slope);
+                self.add_surface(t, h, near, erosion_span(2, 3), w, middle);
+                self.add_surface(t, h, mid_far, erosion_span(2, 3), w, middle_or_badlands);
+                self.add_surface(t, h, coast, erosion_span(3, 4), w, beach);
+                self.add_surface(t, h, near_far, erosion(4), w, middle);
+                self.add_surface(t, h, coast, erosion(5), w, shattered_coast);
+                self.add_surface(t, h, near, erosion(5), w, windswept);
+                self.add_surface(t, h, mid_far, erosion(5), w, middle);
+                self.add_surface(t, h, coast, erosion(6), w, beach);
+
+                if i == 0 {
+                    self.add_surface(t, h, near_far, erosion(6), w, middle);
+                }
+            }
+        }
+    }
+
+    fn add_valleys(&mut self, w: Parameter) {
+        let full = span(FULL_RANGE);
+        let frozen = temperature(0);
+        let unfrozen = span_of(TEMPERATURES[1], TEMPERATURES[4]);
+        let coast = span(COAST_CONTINENTALNESS);
+        let near = span(NEAR_INLAND_CONTINENTALNESS);
+        let coast_far = span_of(COAST_CONTINENTALNESS, FAR_INLAND_CONTINENTALNESS);
+        let inland_far = span_of(INLAND_CONTINENTALNESS, FAR_INLAND_CONTINENTALNESS);
+        let mid_far = span_of(MID_INLAND_CONTINENTALNESS, FAR_INLAND_CONTINENTALNESS);
+
+        let (frozen_shore, shore) = if w.max < 0 {
+            (StonyShore, StonyShore)
+        } else {
+            (FrozenRiver, River)
+        };
+
+        self.add_surface(frozen, full, coast, erosion_span(0, 1), w, frozen_shore);
+        self.add_surface(unfrozen, full, coast, erosion_span(0, 1), w, shore);
+        self.add_surface(frozen, full, near, erosion_span(0, 1), w, FrozenRiver);
+        self.add_surface(unfrozen, full, near, erosion_span(0, 1), w, River);
+        self.add_surface(frozen, full, coast_far, erosion_span(2, 5), w, FrozenRiver);
+        self.add_surface(unfrozen, full, coast_far, erosion_span(2, 5), w, River);
+        self.add_surface(frozen, full, coast, erosion(6), w, FrozenRiver);
+        self.add_surface(unfrozen, full, coast, erosion(6), w, River);
+        self.add_swamps(inland_far, w);
+        self.add_surface(frozen, full, inland_far, erosion(6), w, FrozenRiver);
+
+        for i in 0..TEMPERATURES.len() {
+            for j in 0..HUMIDITIES.len() {
+                let biome = self.pick_middle_or_badlands_if_hot(i, j, w);
+                self.add_surface(temperature(i), humidity(j), mid_far, erosion_span(0, 1), w, biome);
+            }
+        }
+    }
+
+    /// Swamps in temperate climates, mangrove swamps in hot ones.
+    fn add_swamps(&mut self, continentalness: Parameter, w: Parameter) {
+        let full = span(FULL_RANGE);
+
+        self.add_surface(
+            span_of(TEMPERATURES[1], TEMPERATURES[2]),
+            full,
+            continentalness,
+            erosion(6),
+            w,
+            Swamp,
+        );
+        self.add_surface(
+            span_of(TEMPERATURES[3], TEMPERATURES[4]),
+            full,
+            continentalness,
+            erosion(6),
+            w,
+            MangroveSwamp,
+        );
+    }
+
+    fn add_underground(&mut self) {
+        let full = span(FULL_RANGE);
+
+        self.add_underground_biome(full, full, Parameter::span(0.8, 1.0), full, full, DripstoneCaves);
+        self.add_underground_biome(full, Parameter::span(0.7, 1.0), full, full, full, LushCaves);
+
+        if self.version >= Version::V1_19 {
+            let point = ParameterPoint::new(full, full, full, erosion_span(0, 1), Parameter::point(1.1), full, 0.0);
+            self.entries.push((point, DeepDark));
+        }
+    }
+
+    fn pick_middle(&self, i: usize, j: usize, w: Parameter) -> Biome {
+        match MIDDLE_BIOMES_VARIANT[i][j] {
+            Some(variant) if w.max >= 0 => variant,
+            _ => MIDDLE_BIOMES[i][j],
+        }
+    }
+
+    fn pick_middle_or_badlands_if_hot(&self, i: usize, j: usize, w: Parameter) -> Biome {
+        if i == 4 {
+            self.pick_badlands(j, w)
+        } else {
+            self.pick_middle(i, j, w)
+        }
+    }
+
+    fn pick_middle_or_badlands_if_hot_or_slope_if_cold(&self, i: usize, j: usize, w: Parameter) -> Biome {
+        if i == 0 {
+            self.pick_slope(i, j, w)
+        } else {
+            self.pick_middle_or_badlands_if_hot(i, j, w)
+        }
+    }
+
+    fn maybe_pick_windswept_savanna(&self, i: usize, j: usize, w: Parameter, biome: Biome) -> Biome {
+        if i > 1 && j < 4 && w.max >= 0 {
+            WindsweptSavanna
+        } else {
+            biome
+        }
+    }
+
+    fn pick_shattered_coast(&self, i: usize, j: usize, w: Parameter) -> Biome {
+        let biome = if w.max >= 0 {
+            self.pick_middle(i, j, w)
+        } else {
+            self.pick_beach(i, j)
+        };
+
+        self.maybe_pick_windswept_savanna(i, j, w, biome)
+    }
+
+    fn pick_beach(&self, i: usize, _j: usize) -> Biome {
+        match i {
+            0 => SnowyBeach,
+            4 => Desert,
+            _ => Beach,
+        }
+    }
+
+    fn pick_badlands(&self, j: usize, w: Parameter) -> Biome {
+        if j < 2 {
+            if w.max < 0 {
+                Badlands
+            } else {
+                ErodedBadlands
+            }
+        } else if j < 3 {
+            Badlands
+        } else {
+            WoodedBadlands
+        }
+    }
+
+    fn pick_plateau(&self, i: usize, j: usize, w: Parameter) -> Biome {
+        match PLATEAU_BIOMES_VARIANT[i][j] {
+            // cherry groves replaced empty variant slots in 1.20
+            Some(CherryGrove) if self.version < Version::V1_20 => PLATEAU_BIOMES[i][j],
+            Some(variant) if w.max >= 0 => variant,
+            _ => PLATEAU_BIOMES[i][j],
+        }
+    }
+
+    fn pick_peak(&self, i: usize, j: usize, w: Parameter) -> Biome {
+        match i {
+            0..=2 if w.max < 0 => JaggedPeaks,
+            0..=2 => FrozenPeaks,
+            3 => StonyPeaks,
+            _ => self.pick_badlands(j, w),
+        }
+    }
+
+    fn pick_slope(&self, i: usize, j: usize, w: Parameter) -> Biome {
+        if i >= 3 {
+            self.pick_plateau(i, j, w)
+        } else if j <= 1 {
+            SnowySlopes
+        } else {
+            Grove
+        }
+    }
+
+    fn pick_shattered(&self, i: usize, j: usize, w: Parameter) -> Biome {
+        SHATTERED_BIOMES[i][j].unwrap_or_else(|| self.pick_middle(i, j, w))
+    }
+
+    /// Surface biomes are listed twice, at the surface and at the bottom of the world.
+    fn add_surface(&mut self, t: Parameter, h: Parameter, c: Parameter, e: Parameter, w: Parameter, biome: Biome) {
+        let biome = self.biome(biome);
+
+        for depth in [0.0, 1.0] {
+            let point = ParameterPoint::new(t, h, c, e, Parameter::point(depth), w, 0.0);
+            self.entries.push((point, biome));
+        }
+    }
+
+    fn add_underground_biome(
+        &mut self,
+        t: Parameter,
+        h: Parameter,
+        c: Parameter,
+        e: Parameter,
+        w: Parameter,
+        biome: Biome,
+    ) {
+        let point = ParameterPoint::new(t, h, c, e, Parameter::span(0.2, 0.9), w, 0.0);
+        self.entries.push((point, biome));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use crate::biome::{Biome, BiomeSource, TargetPoint};
+    use crate::util::{Point3D, Version};
+
+    use super::{parameters, OverworldBiomeSource};
+
+    fn target(t: f32, h: f32, c: f32, e: f32, d: f32, w: f32) -> TargetPoint {
+        TargetPoint::new(t, h, c, e, d, w)
+    }
+
+    #[test]
+    fn test_parameters() {
+        let list = parameters(Version::V1_20);
+
+        assert_eq!(
+            list.find_value(&target(0.0, 0.0, -1.1, 0.0, 0.0, 0.0)),
+            Biome::MushroomFields
+        );
+        assert_eq!(
+            list.find_value(&target(-0.8, 0.0, -0.6, 0.0, 0.0, 0.0)),
+            Biome::DeepFrozenOcean
+        );
+        assert_eq!(
+            list.find_value(&target(0.8, 0.0, -0.3, 0.0, 0.0, 0.0)),
+            Biome::WarmOcean
+        );
+        assert_eq!(list.find_value(&target(0.8, -0.8, 0.0, 0.0, 0.0, -0.3)), Biome::Desert);
+        assert_eq!(list.find_value(&target(0.0, 0.0, 0.2, 0.0, 0.0, 0.0)), Biome::River);
+        assert_eq!(
+            list.find_value(&target(-0.8, 0.0, 0.8, -0.9, 0.0, 0.6)),
+            Biome::FrozenPeaks
+        );
+        assert_eq!(list.find_value(&target(0.0, 0.0, 0.2, 0.0, 0.5, 0.0)), Biome::River);
+        assert_eq!(
+            list.find_value(&target(0.0, 0.8, 0.2, 0.0, 0.5, -0.3)),
+            Biome::LushCaves
+        );
+        assert_eq!(
+            list.find_value(&target(0.0, 0.0, 0.2, -0.9, 1.1, -0.3)),
+            Biome::DeepDark
+        );
+
+        // hot, eroded, uneroded inland: mangroves since 1.19
+        let swamp = target(0.8, 0.0, 0.2, 0.8, 0.0, -0.3);
+        assert_eq!(list.find_value(&swamp), Biome::MangroveSwamp);
+        assert_eq!(parameters(Version::V1_18).find_value(&swamp), Biome::Swamp);
+
+        // cherry groves since 1.20
+        let cherry = target(-0.3, -0.8, 0.5, -0.3, 0.0, 0.3);
+        assert_eq!(list.find_value(&cherry), Biome::CherryGrove);
+        assert_eq!(parameters(Version::V1_19).find_value(&cherry), Biome::Meadow);
+
+        // every biome is listed
+        let biomes: HashSet<Biome> = list.entries().iter().map(|e| e.1).collect();
+        assert_eq!(biomes.len(), 53);
+        assert!(!parameters(Version::V1_18)
+            .entries()
+            .iter()
+            .any(|e| e.1 == Biome::DeepDark || e.1 == Biome::MangroveSwamp));
+    }
+
+    #[test]
+    fn test_biome_source() {
+        let source = OverworldBiomeSource::new(123, Version::V1_20);
+
+        // the same seed always gives the same biomes
+        let p = Point3D {
+            x: 1000,
+            y: 64,
+            z: -2000,
+        };
+        assert_eq!(
+            source.biome_at(p),
+            OverworldBiomeSource::new(123, Version::V1_20).biome_at(p)
+        );
+
+        // a 4096x4096 block area holds both oceans and several land biomes
+        let mut biomes = HashSet::new();
+        for x in -16..16 {
+            for z in -16..16 {
+                biomes.insert(source.biome_at_quart(x * 32, 16, z * 32));
+            }
+        }
+
+        assert!(biomes.iter().any(|b| b.is_ocean()));
+        assert!(biomes.iter().filter(|b| !b.is_ocean()).count() >= 5);
+
+        // the quart holding a block decides its biome
+        assert_eq!(
+            source.biome_at(Point3D { x: 5, y: 64, z: 7 }),
+            source.biome_at_quart(1, 16, 1)
+        );
+        assert_eq!(
+            source.biome_at(Point3D { x: -1, y: 64, z: -4 }),
+            source.biome_at_quart(-1, 16, -1)
+        );
+    }
+
+    #[test]
+    fn test_climate() {
+        let source = OverworldBiomeSource::new(123, Version::V1_20);
+        let sampler = source.sampler();
+
+        for (x, z) in [(0, 0), (1000, -3000), (-20000, 50000)] {
+            let climate = sampler.sample(x / 4, 16, z / 4);
+
+            // climates stay in a sane range
+            for v in [
+                climate.temperature,
+                climate.humidity,
+                climate.continentalness,
+                climate.erosion,
+                climate.weirdness,
+            ] {
+                assert!(v.abs() < 15000, "{:?}", climate);
+            }
+
+            assert_eq!(
+                (sampler.continentalness(x, z) as f32 * 10000.0) as i64,
+                climate.continentalness
+            );
+        }
+
+        // depth grows downwards
+        let top = sampler.sample(0, 70, 0).depth;
+        let bottom = sampler.sample(0, -14, 0).depth;
+        assert!(top < bottom);
+    }
+
+    #[test]
+    fn test_pinned_seeds() {
+        // pinned from this implementation so that a change to the noises, the spline or the
+        // parameter table shows up, they are not checked against the game or cubiomes yet
+        let expected = [
+            (123, (0, 64, 0), Biome::Taiga),
+            (123, (1000, 64, -2000), Biome::OldGrowthPineTaiga),
+            (123, (-5000, 100, 3000), Biome::River),
+            (123, (20000, -20, -40000), Biome::FrozenRiver),
+            (123, (-123456, 70, 654321), Biome::DeepFrozenOcean),
+            (0, (0, 64, 0), Biome::River),
+            (0, (1000, 64, -2000), Biome::WarmOcean),
+            (0, (-5000, 100, 3000), Biome::SnowyTaiga),
+            (0, (20000, -20, -40000), Biome::DripstoneCaves),
+            (0, (-123456, 70, 654321), Biome::Savanna),
+            (-4172144997902289642, (0, 64, 0), Biome::LukewarmOcean),
+            (-4172144997902289642, (1000, 64, -2000), Biome::DarkForest),
+            (-4172144997902289642, (-5000, 100, 3000), Biome::Plains),
+            (-4172144997902289642, (20000, -20, -40000), Biome::Savanna),
+            (-4172144997902289642, (-123456, 70, 654321), Biome::BirchForest),
+        ];
+
+        for &(seed, (x, y, z), biome) in &expected {
+            let source = OverworldBiomeSource::new(seed, Version::V1_20);
+            assert_eq!(source.biome_at(Point3D { x, y, z }), biome, "{} {:?}", seed, (x, y, z));
+        }
+
+        let climate = |seed, x: i32, y: i32, z: i32| {
+            let t = OverworldBiomeSource::new(seed, Version::V1_20)
+                .sampler()
+                .sample(x >> 2, y >> 2, z >> 2);
+
+            (
+                t.temperature,
+                t.humidity,
+                t.continentalness,
+                t.erosion,
+                t.depth,
+                t.weirdness,
+            )
+        };
+
+        assert_eq!(climate(123, 0, 64, 0), (-7507, 3442, 623, -331, 40, 2710));
+        assert_eq!(climate(0, 20000, -20, -40000), (4148, -893, 5023, 1836, 6625, -2177));
+        assert_eq!(
+            climate(-4172144997902289642, -123456, 70, 654321),
+            (-264, 1135, 5705, 1866, -248, -1796)
+        );
+
+        // deep dark since 1.19 and cherry groves since 1.20
+        let deep = Point3D {
+            x: -5760,
+            y: -40,
+            z: -1920,
+        };
+        let cherry = Point3D {
+            x: -5472,
+            y: 64,
+            z: -1920,
+        };
+        let biomes = |version| {
+            let source = OverworldBiomeSource::new(123, version);
+            (source.biome_at(deep), source.biome_at(cherry))
+        };
+
+        assert_eq!(biomes(Version::V1_18), (Biome::Forest, Biome::Meadow));
+        assert_eq!(biomes(Version::V1_19), (Biome::DeepDark, Biome::Meadow));
+        assert_eq!(biomes(Version::V1_20), (Biome::DeepDark, Biome::CherryGrove));
+    }
+}
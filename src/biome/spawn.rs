@@ -0,0 +1,169 @@
+//! The world spawn from 1.18 on, searched for by climate rather than by biome.
+//!
+//! The game looks for the position closest to the origin whose climate fits the spawn target, then
+//! places the spawn on the first suitable surface block of its chunk. Finding that block needs the
+//! terrain, so only the chunk of the spawn is predicted here.
+//!
+//! see: `Climate.SpawnFinder` and `MinecraftServer.setInitialSpawn` in the vanilla source
+
+use crate::util::{BlockPoint, ChunkPoint, Point3D, Version};
+
+use super::overworld::{spawn_target, ClimateSampler};
+use super::{quart, ParameterPoint, TargetPoint};
+
+/// The spawn of a world, and the spawn chunks around it.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct WorldSpawn {
+    /// The middle of the spawn chunk at `y = 0`. The game moves the spawn to a grass block in this
+    /// chunk, or in one up to 5 chunks away if there is none.
+    pub spawn: BlockPoint,
+    pub chunk: ChunkPoint,
+
+    /// The corners of the spawn chunks processing entities, both included.
+    pub ticking: (ChunkPoint, ChunkPoint),
+}
+
+impl WorldSpawn {
+    /// Predicts the spawn of the world of `seed`. Panics if `version` predates 1.18.
+    pub fn new(seed: i64, version: Version) -> Self {
+        assert!(
+            version >= Version::V1_18,
+            "the climate spawn search needs 1.18 or later"
+        );
+
+        let (x, z) = find_spawn(&ClimateSampler::new(seed));
+        let chunk: ChunkPoint = Point3D { x, y: 0, z }.into();
+
+        let r = spawn_chunk_radius(version) - 1;
+        let ticking = (
+            ChunkPoint {
+                x: chunk.x - r,
+                z: chunk.z - r,
+            },
+            ChunkPoint {
+                x: chunk.x + r,
+                z: chunk.z + r,
+            },
+        );
+
+        WorldSpawn {
+            spawn: Point3D {
+                x: chunk.x * 16 + 8,
+                y: 0,
+                z: chunk.z * 16 + 8,
+            },
+            chunk,
+            ticking,
+        }
+    }
+}
+
+/// The default radius of the chunks kept loaded around the spawn chunk. The spawn chunks are a
+/// square of `2 * radius - 1` chunks processing entities, surrounded by a ring of chunks which only
+/// tick blocks.
+pub fn spawn_chunk_radius(version: Version) -> i32 {
+    // the `spawnChunkRadius` game rule, fixed to 10 before
+    if version >= Version::V1_20_5 {
+        2
+    } else {
+        10
+    }
+}
+
+/// Returns the block column picked by the climate spawn search.
+pub fn find_spawn(sampler: &ClimateSampler) -> (i32, i32) {
+    let target = spawn_target();
+    let mut best = fitness(sampler, &target, 0, 0);
+
+    radial_search(sampler, &target, &mut best, 2048.0, 512.0);
+    radial_search(sampler, &target, &mut best, 512.0, 32.0);
+
+    (best.0, best.1)
+}
+
+/// Walks rings of growing radius around the best position so far, `step` blocks apart. Each ring
+/// uses the position found before it as its center, as the game does.
+fn radial_search(
+    sampler: &ClimateSampler,
+    target: &[ParameterPoint],
+    best: &mut (i32, i32, i64),
+    max_radius: f32,
+    step: f32,
+) {
+    let mut angle = 0f32;
+    let mut radius = step;
+    let (x, z, _) = *best;
+
+    while radius <= max_radius {
+        let i = x + ((angle as f64).sin() * radius as f64) as i32;
+        let j = z + ((angle as f64).cos() * radius as f64) as i32;
+
+        let candidate = fitness(sampler, target, i, j);
+        if candidate.2 < best.2 {
+            *best = candidate;
+        }
+
+        angle += step / radius;
+        if angle as f64 > std::f64::consts::TAU {
+            angle = 0.0;
+            radius += step;
+        }
+    }
+}
+
+/// How well the block column (`x`, `z`) fits the spawn target, lower is better. Positions far from
+/// the origin are penalized.
+fn fitness(sampler: &ClimateSampler, target: &[ParameterPoint], x: i32, z: i32) -> (i32, i32, i64) {
+    let dist = ((x as i64).pow(2) + (z as i64).pow(2)) as f64 / 2500f64.powi(2);
+    let penalty = (10000f64.powi(2) * dist.powi(2)) as i64;
+
+    let climate = TargetPoint {
+        depth: 0,
+        ..sampler.sample(quart(x), 0, quart(z))
+    };
+    let fit = target.iter().map(|p| p.fitness(&climate)).min().unwrap();
+
+    (x, z, penalty + fit)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::biome::overworld::ClimateSampler;
+    use crate::util::{ChunkPoint, Version};
+
+    use super::{find_spawn, fitness, spawn_target, WorldSpawn};
+
+    #[test]
+    fn test_world_spawn() {
+        let spawn = WorldSpawn::new(123, Version::V1_21);
+        let (x, z) = find_spawn(&ClimateSampler::new(123));
+
+        assert_eq!(spawn.chunk, ChunkPoint { x: x >> 4, z: z >> 4 });
+        assert_eq!((spawn.spawn.x >> 4, spawn.spawn.z >> 4), (spawn.chunk.x, spawn.chunk.z));
+        assert_eq!((spawn.spawn.x & 15, spawn.spawn.z & 15), (8, 8));
+
+        // 19 by 19 chunks before 1.20.5, 3 by 3 after
+        let (cx, cz) = (spawn.chunk.x, spawn.chunk.z);
+        let chunk = |x, z| ChunkPoint { x, z };
+
+        let old = WorldSpawn::new(123, Version::V1_18).ticking;
+        assert_eq!(old, (chunk(cx - 9, cz - 9), chunk(cx + 9, cz + 9)));
+        assert_eq!(spawn.ticking, (chunk(cx - 1, cz - 1), chunk(cx + 1, cz + 1)));
+    }
+
+    #[test]
+    fn test_fitness() {
+        let sampler = ClimateSampler::new(123);
+        let target = spawn_target();
+
+        // far away positions are penalized by their squared distance
+        let (_, _, near) = fitness(&sampler, &target, 0, 0);
+        let (_, _, far) = fitness(&sampler, &target, 2500, 0);
+        assert!(near < 100_000_000);
+        assert!(far >= 100_000_000);
+
+        // the spawn fits at least as well as the origin
+        let (x, z) = find_spawn(&sampler);
+        assert!(fitness(&sampler, &target, x, z).2 <= near);
+    }
+}
@@ -0,0 +1,317 @@
+//! The cubic spline shaping the terrain offset of the overworld, as `TerrainProvider.overworldOffset`
+//! in the game. Everything is computed in `f32`, like the game does.
+
+/// The climate values a spline is evaluated against.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct SplineInput {
+    pub continentalness: f32,
+    pub erosion: f32,
+    pub ridges_folded: f32,
+}
+
+/// The climate value a spline is a function of.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Coordinate {
+    Continentalness,
+    Erosion,
+    RidgesFolded,
+}
+
+impl Coordinate {
+    fn apply(&self, input: &SplineInput) -> f32 {
+        match self {
+            Coordinate::Continentalness => input.continentalness,
+            Coordinate::Erosion => input.erosion,
+            Coordinate::RidgesFolded => input.ridges_folded,
+        }
+    }
+}
+
+/// A piecewise cubic function of a climate value, whose points may themselves be splines.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Spline {
+    Constant(f32),
+    Multipoint {
+        coordinate: Coordinate,
+        locations: Vec<f32>,
+        values: Vec<Spline>,
+        derivatives: Vec<f32>,
+    },
+}
+
+impl Spline {
+    fn builder(coordinate: Coordinate) -> SplineBuilder {
+        SplineBuilder {
+            coordinate,
+            locations: Vec::new(),
+            values: Vec::new(),
+            derivatives: Vec::new(),
+        }
+    }
+
+    pub fn apply(&self, input: &SplineInput) -> f32 {
+        let (coordinate, locations, values, derivatives) = match self {
+            Spline::Constant(v) => return *v,
+            Spline::Multipoint {
+                coordinate,
+                locations,
+                values,
+                derivatives,
+            } => (coordinate, locations, values, derivatives),
+        };
+
+        let f = coordinate.apply(input);
+        let last = locations.len() - 1;
+
+        // index of the last location not above f, -1 if there is none
+        let i = locations.iter().take_while(|&&l| l <= f).count() as i32 - 1;
+
+        if i < 0 {
+            return linear_extend(f, locations, values[0].apply(input), derivatives, 0);
+        }
+
+        let i = i as usize;
+        if i == last {
+            return linear_extend(f, locations, values[last].apply(input), derivatives, last);
+        }
+
+        let (l0, l1) = (locations[i], locations[i + 1]);
+        let k = (f - l0) / (l1 - l0);
+
+        let v0 = values[i].apply(input);
+        let v1 = values[i + 1].apply(input);
+        let (d0, d1) = (derivatives[i], derivatives[i + 1]);
+
+        let p = d0 * (l1 - l0) - (v1 - v0);
+        let q = -d1 * (l1 - l0) + (v1 - v0);
+
+        lerp(k, v0, v1) + k * (1f32 - k) * lerp(k, p, q)
+    }
+}
+
+fn linear_extend(f: f32, locations: &[f32], value: f32, derivatives: &[f32], i: usize) -> f32 {
+    let d = derivatives[i];
+
+    if d == 0f32 {
+        value
+    } else {
+        value + d * (f - locations[i])
+    }
+}
+
+fn lerp(delta: f32, start: f32, end: f32) -> f32 {
+    start + delta * (end - start)
+}
+
+struct SplineBuilder {
+    coordinate: Coordinate,
+    locations: Vec<f32>,
+    values: Vec<Spline>,
+    derivatives: Vec<f32>,
+}
+
+impl SplineBuilder {
+    fn point(self, location: f32, value: f32, derivative: f32) -> Self {
+        self.spline(location, Spline::Constant(value), derivative)
+    }
+
+    fn spline(mut self, location: f32, value: Spline, derivative: f32) -> Self {
+        if let Some(&last) = self.locations.last() {
+            assert!(location > last, "locations must be in ascending order");
+        }
+
+        self.locations.push(location);
+        self.values.push(value);
+        self.derivatives.push(derivative);
+        self
+    }
+
+    fn build(self) -> Spline {
+        Spline::Multipoint {
+            coordinate: self.coordinate,
+            locations: self.locations,
+            values: self.values,
+            derivatives: self.derivatives,
+        }
+    }
+}
+
+/// Builds the spline giving the terrain offset from continentalness, erosion and folded ridges.
+pub fn overworld_offset() -> Spline {
+    let s1 = erosion_offset(-0.15, 0.0, 0.0, 0.1, 0.0, -0.03, false);
+    let s2 = erosion_offset(-0.1, 0.03, 0.1, 0.1, 0.01, -0.03, false);
+    let s3 = erosion_offset(-0.1, 0.03, 0.1, 0.7, 0.01, -0.03, true);
+    let s4 = erosion_offset(-0.05, 0.03, 0.1, 1.0, 0.01, 0.01, true);
+
+    Spline::builder(Coordinate::Continentalness)
+        .point(-1.1, 0.044, 0.0)
+        .point(-1.02, -0.2222, 0.0)
+        .point(-0.51, -0.2222, 0.0)
+        .point(-0.44, -0.12, 0.0)
+        .point(-0.18, -0.12, 0.0)
+        .spline(-0.16, s1.clone(), 0.0)
+        .spline(-0.15, s1, 0.0)
+        .spline(-0.1, s2, 0.0)
+        .spline(0.25, s3, 0.0)
+        .spline(1.0, s4, 0.0)
+        .build()
+}
+
+fn erosion_offset(f: f32, g: f32, h: f32, i: f32, j: f32, k: f32, extended: bool) -> Spline {
+    let s1 = mountain_ridge(lerp(i, 0.6, 1.5), extended);
+    let s2 = mountain_ridge(lerp(i, 0.6, 1.0), extended);
+    let s3 = mountain_ridge(i, extended);
+    let s4 = ridge(f - 0.15, 0.5 * i, 0.5 * i, 0.5 * i, i * 0.6, 0.5);
+    let s5 = ridge(f, j * i, g * i, 0.5 * i, i * 0.6, 0.5);
+    let s6 = ridge(f, j, j, g, h, 0.5);
+    let s7 = ridge(f, j, j, g, h, 0.5);
+    let s8 = Spline::builder(Coordinate::RidgesFolded)
+        .point(-1.0, f, 0.0)
+        .spline(-0.4, s6.clone(), 0.0)
+        .point(0.0, h + 0.07, 0.0)
+        .build();
+    let s9 = ridge(-0.02, k, k, g, h, 0.0);
+
+    let mut builder = Spline::builder(Coordinate::Erosion)
+        .spline(-0.85, s1, 0.0)
+        .spline(-0.7, s2, 0.0)
+        .spline(-0.4, s3, 0.0)
+        .spline(-0.35, s4, 0.0)
+        .spline(-0.1, s5, 0.0)
+        .spline(0.2, s6, 0.0);
+
+    if extended {
+        builder = builder
+            .spline(0.4, s7.clone(), 0.0)
+            .spline(0.45, s8.clone(), 0.0)
+            .spline(0.55, s8, 0.0)
+            .spline(0.58, s7, 0.0);
+    }
+
+    builder.spline(0.7, s9, 0.0).build()
+}
+
+fn mountain_ridge(f: f32, extended: bool) -> Spline {
+    let builder = Spline::builder(Coordinate::RidgesFolded);
+
+    let i = mountain_continentalness(-1.0, f, -0.7);
+    let k = mountain_continentalness(1.0, f, -0.7);
+    let l = mountain_ridge_zero_continentalness(f);
+
+    if -0.65 < l && l < 1.0 {
+        let n = mountain_continentalness(-0.65, f, -0.7);
+        let p = mountain_continentalness(-0.75, f, -0.7);
+        let q = slope(i, p, -1.0, -0.75);
+        let r = mountain_continentalness(l, f, -0.7);
+        let s = slope(r, k, l, 1.0);
+
+        builder
+            .point(-1.0, i, q)
+            .point(-0.75, p, 0.0)
+            .point(-0.65, n, 0.0)
+            .point(l - 0.01, r, 0.0)
+            .point(l, r, s)
+            .point(1.0, k, s)
+            .build()
+    } else {
+        let n = slope(i, k, -1.0, 1.0);
+
+        let builder = if extended {
+            builder.point(-1.0, i.max(0.2), 0.0).point(0.0, lerp(0.5, i, k), n)
+        } else {
+            builder.point(-1.0, i, n)
+        };
+
+        builder.point(1.0, k, n).build()
+    }
+}
+
+fn mountain_continentalness(h: f32, f: f32, g: f32) -> f32 {
+    let i = 1.0 - (1.0 - f) * 0.5;
+    let j = 0.5 * (1.0 - f);
+    let l = (h + 1.17) * 0.46082947 * i - j;
+
+    if h < g {
+        l.max(-0.2222)
+    } else {
+        l.max(0.0)
+    }
+}
+
+fn mountain_ridge_zero_continentalness(f: f32) -> f32 {
+    let g = 1.0 - (1.0 - f) * 0.5;
+    let h = 0.5 * (1.0 - f);
+
+    h / (0.46082947 * g) - 1.17
+}
+
+fn ridge(f: f32, g: f32, h: f32, i: f32, j: f32, k: f32) -> Spline {
+    let m = (0.5 * (g - f)).max(k);
+    let n = 5.0 * (h - g);
+
+    Spline::builder(Coordinate::RidgesFolded)
+        .point(-1.0, f, m)
+        .point(-0.4, g, m.min(n))
+        .point(0.0, h, n)
+        .point(0.4, i, 2.0 * (i - h))
+        .point(1.0, j, 0.7 * (j - i))
+        .build()
+}
+
+fn slope(f: f32, g: f32, h: f32, i: f32) -> f32 {
+    (g - f) / (i - h)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{overworld_offset, SplineInput};
+
+    fn offset(continentalness: f32, erosion: f32, ridges_folded: f32) -> f32 {
+        overworld_offset().apply(&SplineInput {
+            continentalness,
+            erosion,
+            ridges_folded,
+        })
+    }
+
+    #[test]
+    fn test_overworld_offset() {
+        // constant parts of the ocean floor
+        assert_eq!(offset(-1.5, 0.0, 0.0), 0.044);
+        assert_eq!(offset(-1.02, 0.3, -0.4), -0.2222);
+        assert_eq!(offset(-0.8, -1.0, 1.0), -0.2222);
+        assert_eq!(offset(-0.3, 0.0, 0.0), -0.12);
+
+        // between two constants
+        let mid = offset(-0.475, 0.0, 0.0);
+        assert!(-0.2222 < mid && mid < -0.12);
+
+        // exact values across every continentalness and erosion segment, checked against a separate
+        // transcription of `TerrainProvider.overworldOffset` run on the JVM
+        let expected = [
+            ((-1.06, 0.0, 0.0), -0.089100294),
+            ((-0.475, 0.0, 0.0), -0.1711),
+            ((-0.17, 0.0, 0.0), -0.06),
+            ((-0.155, -0.5, 0.3), 0.0973477),
+            ((-0.12, 0.1, -0.6), -0.004735996),
+            ((0.0, -0.8, 0.5), 0.56490874),
+            ((0.1, 0.5, -0.2), 0.048311375),
+            ((0.3, -0.9, 1.0), 1.23344),
+            ((0.5, -0.9, -1.0), 0.24009216),
+            ((0.5, 0.5, -0.5), 0.0025866171),
+            ((0.7, 0.42, -0.3), 0.01879999),
+            ((0.8, -0.375, 0.2), 0.5310515),
+            ((1.0, -0.2, 0.7), 0.590434),
+            ((1.2, 0.9, 0.05), 0.010640624),
+        ];
+
+        for &((c, e, r), value) in &expected {
+            assert_eq!(offset(c, e, r), value, "{:?}", (c, e, r));
+        }
+
+        // inland, eroded terrain stays low while uneroded peaks go up
+        assert!(offset(0.5, 0.8, 0.0) < 0.1);
+        assert!(offset(0.5, -0.9, 1.0) > 0.5);
+        assert!(offset(0.5, -0.9, 1.0) > offset(0.5, -0.9, -1.0));
+    }
+}
@@ -0,0 +1,147 @@
+//! Block arguments of commands: an id, block state properties and block entity data.
+
+use std::fmt::{self, Display};
+
+use crate::util::Version;
+
+use super::syntax::{Syntax, Versioned, LATEST};
+use super::Nbt;
+
+/// A block as written in commands, `minecraft:chest[facing=north]{Lock:"key"}` for example.
+///
+/// Before 1.13 the state is either the properties or a data value, `stone 1` for example, and
+/// commands write the block entity data apart.
+#[derive(Clone, PartialEq, Debug)]
+pub struct BlockState {
+    pub name: String,
+    pub properties: Vec<(String, String)>,
+    /// The data value, before 1.13 only.
+    pub data: Option<u8>,
+    pub nbt: Option<Nbt>,
+}
+
+impl BlockState {
+    pub fn new(name: &str) -> Self {
+        BlockState {
+            name: name.to_string(),
+            properties: Vec::new(),
+            data: None,
+            nbt: None,
+        }
+    }
+
+    /// Sets the block state property `key`, replacing any previous value.
+    pub fn with_property(mut self, key: &str, value: &str) -> Self {
+        match self.properties.iter_mut().find(|(k, _)| k == key) {
+            Some(property) => property.1 = value.to_string(),
+            None => self.properties.push((key.to_string(), value.to_string())),
+        }
+
+        self
+    }
+
+    pub fn with_data(mut self, data: u8) -> Self {
+        self.data = Some(data);
+        self
+    }
+
+    pub fn with_nbt(mut self, nbt: Nbt) -> Self {
+        self.nbt = Some(nbt);
+        self
+    }
+
+    /// The block for `version`, see `Display`. Before 1.13 the block entity data is left out.
+    /// Panics if a data value is set from 1.13 on.
+    pub fn display(&self, version: Version) -> impl Display + '_ {
+        Versioned(self, version)
+    }
+
+    /// The data value or properties, as written after the name before 1.13.
+    pub(super) fn legacy_state(&self) -> Option<String> {
+        match self.data {
+            Some(data) => Some(data.to_string()),
+            None if self.properties.is_empty() => None,
+            None => Some(self.joined_properties()),
+        }
+    }
+
+    fn joined_properties(&self) -> String {
+        let properties: Vec<String> = self.properties.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+        properties.join(",")
+    }
+}
+
+impl From<&str> for BlockState {
+    /// The block `name`, without properties or data.
+    fn from(name: &str) -> Self {
+        BlockState::new(name)
+    }
+}
+
+impl Display for BlockState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, LATEST)
+    }
+}
+
+impl Syntax for BlockState {
+    fn write(&self, f: &mut fmt::Formatter<'_>, version: Version) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+
+        if version < Version::V1_13 {
+            return match self.legacy_state() {
+                Some(state) => write!(f, " {}", state),
+                None => Ok(()),
+            };
+        }
+
+        assert!(self.data.is_none(), "data values were removed in 1.13: {}", self.name);
+
+        if !self.properties.is_empty() {
+            write!(f, "[{}]", self.joined_properties())?;
+        }
+
+        if let Some(nbt) = &self.nbt {
+            write!(f, "{}", nbt.display(version))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::cmd::Nbt;
+    use crate::util::Version;
+
+    use super::BlockState;
+
+    #[test]
+    fn test_block_state() {
+        assert_eq!(BlockState::from("stone").to_string(), "stone");
+
+        let chest = BlockState::new("minecraft:chest")
+            .with_property("facing", "north")
+            .with_property("waterlogged", "false")
+            .with_property("facing", "east")
+            .with_nbt(Nbt::compound().with("Lock", "key"));
+
+        assert_eq!(
+            chest.to_string(),
+            r#"minecraft:chest[facing=east,waterlogged=false]{Lock:"key"}"#
+        );
+        assert_eq!(
+            chest.display(Version::V1_12).to_string(),
+            "minecraft:chest facing=east,waterlogged=false"
+        );
+
+        let granite = BlockState::new("stone").with_data(1);
+        assert_eq!(granite.display(Version::V1_12).to_string(), "stone 1");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_flattened_data() {
+        BlockState::new("stone").with_data(1).to_string();
+    }
+}
@@ -0,0 +1,887 @@
+//! The commands generated by this module, as typed values serialized by `Display`.
+//!
+//! Entity selectors, objectives and NBT paths are kept as plain text, `@e[type=minecraft:item]`
+//! for example.
+
+use std::fmt::{self, Display};
+
+use crate::util::Version;
+
+use super::syntax::{Syntax, Versioned, LATEST};
+use super::{BlockState, Item, Nbt, Position, Rotation};
+
+/// An in game command, without the leading `/`.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Command {
+    SetBlock {
+        pos: Position,
+        block: BlockState,
+        mode: SetBlockMode,
+    },
+    Fill {
+        from: Position,
+        to: Position,
+        block: BlockState,
+        mode: FillMode,
+    },
+    Clone {
+        from: Position,
+        to: Position,
+        destination: Position,
+        mask: CloneMask,
+        mode: CloneMode,
+    },
+    /// Summons `entity` at `pos`, or at the executor if there is none.
+    Summon {
+        entity: String,
+        pos: Option<Position>,
+        nbt: Option<Nbt>,
+    },
+    Kill {
+        targets: String,
+    },
+    Give {
+        targets: String,
+        item: Item,
+    },
+    /// Teleports `targets` to `pos`, or the executor if there are none.
+    Teleport {
+        targets: Option<String>,
+        pos: Position,
+        rotation: Option<Rotation>,
+    },
+    /// Runs `run` once per context the subcommands produce. Without `run`, the last subcommand
+    /// must be a condition.
+    Execute {
+        subcommands: Vec<Execute>,
+        run: Option<Box<Command>>,
+    },
+    Data(Data),
+    Scoreboard(Scoreboard),
+    /// A command the model doesn't cover, written as is.
+    Raw(String),
+}
+
+/// How `setblock` treats the block it replaces.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum SetBlockMode {
+    #[default]
+    Replace,
+    /// Drops the replaced block as if mined.
+    Destroy,
+    /// Only places the block into air.
+    Keep,
+}
+
+/// Which blocks `fill` replaces.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub enum FillMode {
+    /// Every block, or only those matching the filter.
+    #[default]
+    Replace,
+    ReplaceOnly(BlockState),
+    Destroy,
+    /// Only the outer layer, filling the inside with air.
+    Hollow,
+    /// Only air blocks.
+    Keep,
+    /// Only the outer layer, leaving the inside alone.
+    Outline,
+}
+
+/// Which source blocks `clone` copies.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub enum CloneMask {
+    #[default]
+    Replace,
+    /// Every block but air.
+    Masked,
+    Filtered(BlockState),
+}
+
+/// How `clone` treats overlapping and source regions.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum CloneMode {
+    #[default]
+    Normal,
+    /// Allows the source and destination to overlap.
+    Force,
+    /// Replaces the source with air.
+    Move,
+}
+
+/// A subcommand of `execute`.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Execute {
+    As(String),
+    At(String),
+    Positioned(Position),
+    PositionedAs(String),
+    Rotated(Rotation),
+    RotatedAs(String),
+    Facing(Position),
+    /// Floors the position along the given axes, `xz` for example.
+    Align(String),
+    /// Whether local coordinates start at the `eyes` or `feet` of the executor.
+    Anchored(String),
+    In(String),
+    If(Condition),
+    Unless(Condition),
+}
+
+/// A condition of `execute if` and `execute unless`.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Condition {
+    Block(Position, BlockState),
+    Entity(String),
+    /// The score of `target` is within `range`, `1..` for example.
+    Score {
+        target: String,
+        objective: String,
+        range: String,
+    },
+}
+
+/// What the `data` command reads or writes.
+#[derive(Clone, PartialEq, Debug)]
+pub enum DataTarget {
+    Block(Position),
+    Entity(String),
+    Storage(String),
+}
+
+/// The `data` command.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Data {
+    Get {
+        target: DataTarget,
+        path: Option<String>,
+        scale: Option<f64>,
+    },
+    Merge {
+        target: DataTarget,
+        nbt: Nbt,
+    },
+    Remove {
+        target: DataTarget,
+        path: String,
+    },
+}
+
+/// The `scoreboard` command.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Scoreboard {
+    AddObjective {
+        name: String,
+        criteria: String,
+    },
+    RemoveObjective(String),
+    SetDisplay {
+        slot: String,
+        objective: Option<String>,
+    },
+    Set {
+        targets: String,
+        objective: String,
+        value: i32,
+    },
+    Add {
+        targets: String,
+        objective: String,
+        value: i32,
+    },
+    Remove {
+        targets: String,
+        objective: String,
+        value: i32,
+    },
+    Reset {
+        targets: String,
+        objective: Option<String>,
+    },
+    /// Applies `operation` (`+=`, `<`, `><`, ...) to the scores of `targets` with those of `source`.
+    Operation {
+        targets: String,
+        objective: String,
+        operation: String,
+        source: String,
+        source_objective: String,
+    },
+}
+
+impl Command {
+    /// The command for `version`, see `Display`. Panics if the command can't be written for
+    /// `version`: before 1.13 only `execute` as an entity and at itself, optionally positioned and
+    /// if a block, and `data merge` on blocks and entities are, and positions can't be local.
+    pub fn display(&self, version: Version) -> impl Display + '_ {
+        Versioned(self, version)
+    }
+
+    /// The command before 1.13.
+    fn write_legacy(&self, f: &mut fmt::Formatter<'_>, version: Version) -> fmt::Result {
+        match self {
+            Command::SetBlock { pos, block, mode } => {
+                write!(f, "setblock {} {}", pos.display(version), block.name)?;
+
+                let mode = match mode {
+                    SetBlockMode::Replace => None,
+                    SetBlockMode::Destroy => Some("destroy".to_string()),
+                    SetBlockMode::Keep => Some("keep".to_string()),
+                };
+                let nbt = block.nbt.as_ref().map(|nbt| nbt.display(version).to_string());
+
+                write_optional(f, &[("0", block.legacy_state()), ("replace", mode), ("", nbt)])
+            }
+            Command::Fill { from, to, block, mode } => {
+                write!(
+                    f,
+                    "fill {} {} {}",
+                    from.display(version),
+                    to.display(version),
+                    block.name
+                )?;
+
+                let mode = match mode {
+                    FillMode::Replace => None,
+                    FillMode::ReplaceOnly(filter) => {
+                        assert!(block.nbt.is_none(), "filtered fills can't set block data before 1.13");
+
+                        let filter_state = filter.legacy_state().unwrap_or_else(|| "-1".to_string());
+                        let state = block.legacy_state().unwrap_or_else(|| "0".to_string());
+
+                        return write!(f, " {} replace {} {}", state, filter.name, filter_state);
+                    }
+                    FillMode::Destroy => Some("destroy"),
+                    FillMode::Hollow => Some("hollow"),
+                    FillMode::Keep => Some("keep"),
+                    FillMode::Outline => Some("outline"),
+                };
+                let nbt = block.nbt.as_ref().map(|nbt| nbt.display(version).to_string());
+
+                write_optional(
+                    f,
+                    &[
+                        ("0", block.legacy_state()),
+                        ("replace", mode.map(str::to_string)),
+                        ("", nbt),
+                    ],
+                )
+            }
+            Command::Clone {
+                from,
+                to,
+                destination,
+                mask,
+                mode,
+            } => {
+                write!(
+                    f,
+                    "clone {} {} {}",
+                    from.display(version),
+                    to.display(version),
+                    destination.display(version)
+                )?;
+
+                let (mask, filter) = match mask {
+                    CloneMask::Replace => (None, None),
+                    CloneMask::Masked => (Some("masked"), None),
+                    CloneMask::Filtered(filter) => (Some("filtered"), Some(filter)),
+                };
+                let mode = match mode {
+                    CloneMode::Normal => None,
+                    CloneMode::Force => Some("force"),
+                    CloneMode::Move => Some("move"),
+                };
+
+                write_optional(
+                    f,
+                    &[
+                        ("replace", mask.map(str::to_string)),
+                        ("normal", mode.map(str::to_string)),
+                        ("", filter.map(|filter| filter.display(version).to_string())),
+                    ],
+                )
+            }
+            Command::Give { targets, item } => {
+                write!(f, "give {} {}", targets, item.name)?;
+
+                let count = Some(item.count).filter(|&count| count != 1);
+                let damage = Some(item.damage()).filter(|&damage| damage != 0);
+                let tag = item.tag(version).filter(|tag| *tag != Nbt::compound());
+
+                write_optional(
+                    f,
+                    &[
+                        ("1", count.map(|count| count.to_string())),
+                        ("0", damage.map(|damage| damage.to_string())),
+                        ("", tag.map(|tag| tag.display(version).to_string())),
+                    ],
+                )
+            }
+            Command::Execute { subcommands, run } => {
+                let (targets, rest) = match subcommands.as_slice() {
+                    // the legacy command runs as the targets too, unlike `at` alone
+                    [Execute::As(targets), Execute::At(at), rest @ ..] if at == "@s" => (targets, rest),
+                    _ => panic!("no execute before 1.13 for: {}", self),
+                };
+
+                let (pos, rest) = match rest {
+                    [Execute::Positioned(pos), rest @ ..] => (*pos, rest),
+                    rest => (Position::here(), rest),
+                };
+
+                write!(f, "execute {} {}", targets, pos.display(version))?;
+
+                match rest {
+                    [] => {}
+                    [Execute::If(Condition::Block(pos, block))] => {
+                        let state = block.legacy_state().unwrap_or_else(|| "-1".to_string());
+                        write!(f, " detect {} {} {}", pos.display(version), block.name, state)?;
+                    }
+                    _ => panic!("no execute before 1.13 for: {}", self),
+                }
+
+                match run {
+                    Some(cmd) => write!(f, " {}", cmd.display(version)),
+                    None => panic!("no execute before 1.13 for: {}", self),
+                }
+            }
+            Command::Data(Data::Merge {
+                target: DataTarget::Block(pos),
+                nbt,
+            }) => write!(f, "blockdata {} {}", pos.display(version), nbt.display(version)),
+            Command::Data(Data::Merge {
+                target: DataTarget::Entity(targets),
+                nbt,
+            }) => write!(f, "entitydata {} {}", targets, nbt.display(version)),
+            Command::Data(_) => panic!("no data command before 1.13 for: {}", self),
+            Command::Summon { pos: Some(pos), .. } | Command::Teleport { pos, .. } if pos.is_local() => {
+                panic!("no local positions before 1.13: {}", self)
+            }
+            // the same as from 1.13 on
+            _ => self.write(f, Version::V1_13),
+        }
+    }
+}
+
+/// Writes the trailing optional arguments of a command before 1.13, each given as its default and
+/// value. Defaults are written for the arguments before the last value.
+fn write_optional(f: &mut fmt::Formatter<'_>, args: &[(&str, Option<String>)]) -> fmt::Result {
+    let count = args.iter().rposition(|(_, value)| value.is_some()).map_or(0, |i| i + 1);
+
+    for (default, value) in &args[..count] {
+        write!(f, " {}", value.as_deref().unwrap_or(default))?;
+    }
+
+    Ok(())
+}
+
+impl Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, LATEST)
+    }
+}
+
+impl Syntax for Command {
+    fn write(&self, f: &mut fmt::Formatter<'_>, version: Version) -> fmt::Result {
+        if version < Version::V1_13 {
+            return self.write_legacy(f, version);
+        }
+
+        match self {
+            Command::SetBlock { pos, block, mode } => {
+                write!(f, "setblock {} {}", pos, block.display(version))?;
+
+                match mode {
+                    SetBlockMode::Replace => Ok(()),
+                    SetBlockMode::Destroy => write!(f, " destroy"),
+                    SetBlockMode::Keep => write!(f, " keep"),
+                }
+            }
+            Command::Fill { from, to, block, mode } => {
+                write!(f, "fill {} {} {}", from, to, block.display(version))?;
+
+                match mode {
+                    FillMode::Replace => Ok(()),
+                    FillMode::ReplaceOnly(filter) => write!(f, " replace {}", filter.display(version)),
+                    FillMode::Destroy => write!(f, " destroy"),
+                    FillMode::Hollow => write!(f, " hollow"),
+                    FillMode::Keep => write!(f, " keep"),
+                    FillMode::Outline => write!(f, " outline"),
+                }
+            }
+            Command::Clone {
+                from,
+                to,
+                destination,
+                mask,
+                mode,
+            } => {
+                write!(f, "clone {} {} {}", from, to, destination)?;
+
+                let mode = match mode {
+                    CloneMode::Normal => "",
+                    CloneMode::Force => " force",
+                    CloneMode::Move => " move",
+                };
+
+                match mask {
+                    CloneMask::Replace if mode.is_empty() => Ok(()),
+                    CloneMask::Replace => write!(f, " replace{}", mode),
+                    CloneMask::Masked => write!(f, " masked{}", mode),
+                    CloneMask::Filtered(filter) => write!(f, " filtered {}{}", filter.display(version), mode),
+                }
+            }
+            Command::Summon { entity, pos, nbt } => {
+                write!(f, "summon {}", entity)?;
+
+                match (pos, nbt) {
+                    (Some(pos), Some(nbt)) => write!(f, " {} {}", pos, nbt.display(version)),
+                    // data can only follow a position
+                    (None, Some(nbt)) => write!(f, " {} {}", Position::here(), nbt.display(version)),
+                    (Some(pos), None) => write!(f, " {}", pos),
+                    (None, None) => Ok(()),
+                }
+            }
+            Command::Kill { targets } => write!(f, "kill {}", targets),
+            Command::Give { targets, item } => {
+                write!(f, "give {} {}", targets, item.display(version))?;
+
+                match item.count {
+                    1 => Ok(()),
+                    count => write!(f, " {}", count),
+                }
+            }
+            Command::Teleport { targets, pos, rotation } => {
+                write!(f, "tp")?;
+
+                if let Some(targets) = targets {
+                    write!(f, " {}", targets)?;
+                }
+
+                write!(f, " {}", pos)?;
+
+                match rotation {
+                    Some(rotation) => write!(f, " {}", rotation),
+                    None => Ok(()),
+                }
+            }
+            Command::Execute { subcommands, run } => {
+                write!(f, "execute")?;
+
+                for subcommand in subcommands {
+                    write!(f, " {}", Versioned(subcommand, version))?;
+                }
+
+                match run {
+                    Some(cmd) => write!(f, " run {}", cmd.display(version)),
+                    None => Ok(()),
+                }
+            }
+            Command::Data(data) => write!(f, "data {}", Versioned(data, version)),
+            Command::Scoreboard(scoreboard) => write!(f, "scoreboard {}", scoreboard),
+            Command::Raw(cmd) => write!(f, "{}", cmd),
+        }
+    }
+}
+
+impl Display for Execute {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, LATEST)
+    }
+}
+
+impl Syntax for Execute {
+    fn write(&self, f: &mut fmt::Formatter<'_>, version: Version) -> fmt::Result {
+        match self {
+            Execute::As(targets) => write!(f, "as {}", targets),
+            Execute::At(targets) => write!(f, "at {}", targets),
+            Execute::Positioned(pos) => write!(f, "positioned {}", pos),
+            Execute::PositionedAs(targets) => write!(f, "positioned as {}", targets),
+            Execute::Rotated(rotation) => write!(f, "rotated {}", rotation),
+            Execute::RotatedAs(targets) => write!(f, "rotated as {}", targets),
+            Execute::Facing(pos) => write!(f, "facing {}", pos),
+            Execute::Align(axes) => write!(f, "align {}", axes),
+            Execute::Anchored(anchor) => write!(f, "anchored {}", anchor),
+            Execute::In(dimension) => write!(f, "in {}", dimension),
+            Execute::If(condition) => write!(f, "if {}", Versioned(condition, version)),
+            Execute::Unless(condition) => write!(f, "unless {}", Versioned(condition, version)),
+        }
+    }
+}
+
+impl Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, LATEST)
+    }
+}
+
+impl Syntax for Condition {
+    fn write(&self, f: &mut fmt::Formatter<'_>, version: Version) -> fmt::Result {
+        match self {
+            Condition::Block(pos, block) => write!(f, "block {} {}", pos, block.display(version)),
+            Condition::Entity(targets) => write!(f, "entity {}", targets),
+            Condition::Score {
+                target,
+                objective,
+                range,
+            } => write!(f, "score {} {} matches {}", target, objective, range),
+        }
+    }
+}
+
+impl Display for DataTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataTarget::Block(pos) => write!(f, "block {}", pos),
+            DataTarget::Entity(targets) => write!(f, "entity {}", targets),
+            DataTarget::Storage(id) => write!(f, "storage {}", id),
+        }
+    }
+}
+
+impl Display for Data {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, LATEST)
+    }
+}
+
+impl Syntax for Data {
+    fn write(&self, f: &mut fmt::Formatter<'_>, version: Version) -> fmt::Result {
+        match self {
+            Data::Get { target, path, scale } => {
+                write!(f, "get {}", target)?;
+
+                if let Some(path) = path {
+                    write!(f, " {}", path)?;
+
+                    if let Some(scale) = scale {
+                        write!(f, " {}", scale)?;
+                    }
+                }
+
+                Ok(())
+            }
+            Data::Merge { target, nbt } => write!(f, "merge {} {}", target, nbt.display(version)),
+            Data::Remove { target, path } => write!(f, "remove {} {}", target, path),
+        }
+    }
+}
+
+impl Display for Scoreboard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Scoreboard::AddObjective { name, criteria } => write!(f, "objectives add {} {}", name, criteria),
+            Scoreboard::RemoveObjective(name) => write!(f, "objectives remove {}", name),
+            Scoreboard::SetDisplay { slot, objective } => {
+                write!(f, "objectives setdisplay {}", slot)?;
+
+                match objective {
+                    Some(objective) => write!(f, " {}", objective),
+                    None => Ok(()),
+                }
+            }
+            Scoreboard::Set {
+                targets,
+                objective,
+                value,
+            } => write!(f, "players set {} {} {}", targets, objective, value),
+            Scoreboard::Add {
+                targets,
+                objective,
+                value,
+            } => write!(f, "players add {} {} {}", targets, objective, value),
+            Scoreboard::Remove {
+                targets,
+                objective,
+                value,
+            } => write!(f, "players remove {} {} {}", targets, objective, value),
+            Scoreboard::Reset { targets, objective } => {
+                write!(f, "players reset {}", targets)?;
+
+                match objective {
+                    Some(objective) => write!(f, " {}", objective),
+                    None => Ok(()),
+                }
+            }
+            Scoreboard::Operation {
+                targets,
+                objective,
+                operation,
+                source,
+                source_objective,
+            } => write!(
+                f,
+                "players operation {} {} {} {} {}",
+                targets, objective, operation, source, source_objective
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::cmd::{BlockState, Item, Nbt, Position, Rotation};
+    use crate::util::Version;
+
+    use super::{
+        CloneMask, CloneMode, Command, Condition, Data, DataTarget, Execute, FillMode, Scoreboard, SetBlockMode,
+    };
+
+    #[test]
+    fn test_block_commands() {
+        let setblock = Command::SetBlock {
+            pos: Position::relative(0.0, 1.0, 0.0),
+            block: BlockState::new("redstone_block"),
+            mode: SetBlockMode::Keep,
+        };
+        assert_eq!(setblock.to_string(), "setblock ~ ~1 ~ redstone_block keep");
+
+        let fill = Command::Fill {
+            from: Position::absolute(0.0, -64.0, 0.0),
+            to: Position::absolute(15.0, -60.0, 15.0),
+            block: "air".into(),
+            mode: FillMode::ReplaceOnly("minecraft:water".into()),
+        };
+        assert_eq!(fill.to_string(), "fill 0 -64 0 15 -60 15 air replace minecraft:water");
+
+        let clone = |mask, mode| {
+            Command::Clone {
+                from: Position::absolute(0.0, 0.0, 0.0),
+                to: Position::absolute(1.0, 1.0, 1.0),
+                destination: Position::local(0.0, 0.0, 5.0),
+                mask,
+                mode,
+            }
+            .to_string()
+        };
+        assert_eq!(clone(CloneMask::Replace, CloneMode::Normal), "clone 0 0 0 1 1 1 ^ ^ ^5");
+        assert_eq!(
+            clone(CloneMask::Replace, CloneMode::Move),
+            "clone 0 0 0 1 1 1 ^ ^ ^5 replace move"
+        );
+        assert_eq!(
+            clone(CloneMask::Filtered("stone".into()), CloneMode::Force),
+            "clone 0 0 0 1 1 1 ^ ^ ^5 filtered stone force"
+        );
+    }
+
+    #[test]
+    fn test_entity_commands() {
+        let summon = Command::Summon {
+            entity: "falling_block".to_string(),
+            pos: None,
+            nbt: Some(Nbt::compound().with("Time", 1)),
+        };
+        assert_eq!(summon.to_string(), "summon falling_block ~ ~ ~ {Time:1}");
+
+        let tp = Command::Teleport {
+            targets: Some("@p".to_string()),
+            pos: Position::absolute(0.5, 64.0, 0.5),
+            rotation: Some(Rotation::absolute(180.0, 0.0)),
+        };
+        assert_eq!(tp.to_string(), "tp @p 0.5 64 0.5 180 0");
+
+        let execute = Command::Execute {
+            subcommands: vec![
+                Execute::As("@a".to_string()),
+                Execute::At("@s".to_string()),
+                Execute::If(Condition::Block(
+                    Position::relative(0.0, -1.0, 0.0),
+                    "grass_block".into(),
+                )),
+            ],
+            run: Some(Box::new(Command::Kill {
+                targets: "@e[type=item,distance=..4]".to_string(),
+            })),
+        };
+        assert_eq!(
+            execute.to_string(),
+            "execute as @a at @s if block ~ ~-1 ~ grass_block run kill @e[type=item,distance=..4]"
+        );
+    }
+
+    #[test]
+    fn test_data_and_scoreboard() {
+        let merge = Command::Data(Data::Merge {
+            target: DataTarget::Block(Position::absolute(1.0, 2.0, 3.0)),
+            nbt: Nbt::compound().with("auto", true),
+        });
+        assert_eq!(merge.to_string(), "data merge block 1 2 3 {auto:1b}");
+
+        let get = Command::Data(Data::Get {
+            target: DataTarget::Entity("@s".to_string()),
+            path: Some("Pos[1]".to_string()),
+            scale: Some(10.0),
+        });
+        assert_eq!(get.to_string(), "data get entity @s Pos[1] 10");
+
+        let operation = Command::Scoreboard(Scoreboard::Operation {
+            targets: "@s".to_string(),
+            objective: "timer".to_string(),
+            operation: "%=".to_string(),
+            source: "#20".to_string(),
+            source_objective: "const".to_string(),
+        });
+        assert_eq!(
+            operation.to_string(),
+            "scoreboard players operation @s timer %= #20 const"
+        );
+    }
+
+    #[test]
+    fn test_legacy_syntax() {
+        let v = Version::V1_12;
+
+        let setblock = Command::SetBlock {
+            pos: Position::relative(0.0, 1.0, 0.0),
+            block: BlockState::new("redstone_block"),
+            mode: SetBlockMode::Keep,
+        };
+        assert_eq!(setblock.display(v).to_string(), "setblock ~ ~1 ~ redstone_block 0 keep");
+
+        let chest = Command::SetBlock {
+            pos: Position::absolute(1.0, 2.0, 3.0),
+            block: BlockState::new("chest")
+                .with_property("facing", "east")
+                .with_nbt(Nbt::compound().with("Lock", r#"a "key""#)),
+            mode: SetBlockMode::Replace,
+        };
+        assert_eq!(
+            chest.display(v).to_string(),
+            r#"setblock 1 2 3 chest facing=east replace {Lock:"a \"key\""}"#
+        );
+        assert_eq!(
+            chest.display(Version::V1_13).to_string(),
+            r#"setblock 1 2 3 chest[facing=east]{Lock:"a \"key\""}"#
+        );
+        assert_eq!(
+            chest.to_string(),
+            r#"setblock 1 2 3 chest[facing=east]{Lock:'a "key"'}"#
+        );
+
+        let fill = |block: BlockState, mode| Command::Fill {
+            from: Position::absolute(0.0, 0.0, 0.0),
+            to: Position::absolute(15.0, 4.0, 15.0),
+            block,
+            mode,
+        };
+        assert_eq!(
+            fill("air".into(), FillMode::Replace).display(v).to_string(),
+            "fill 0 0 0 15 4 15 air"
+        );
+        assert_eq!(
+            fill(BlockState::new("stone").with_data(1), FillMode::Hollow)
+                .display(v)
+                .to_string(),
+            "fill 0 0 0 15 4 15 stone 1 hollow"
+        );
+        assert_eq!(
+            fill("air".into(), FillMode::ReplaceOnly("water".into()))
+                .display(v)
+                .to_string(),
+            "fill 0 0 0 15 4 15 air 0 replace water -1"
+        );
+
+        let clone = Command::Clone {
+            from: Position::absolute(0.0, 0.0, 0.0),
+            to: Position::absolute(1.0, 1.0, 1.0),
+            destination: Position::relative(0.0, 0.0, 5.0),
+            mask: CloneMask::Filtered(BlockState::new("wool").with_data(14)),
+            mode: CloneMode::Normal,
+        };
+        assert_eq!(
+            clone.display(v).to_string(),
+            "clone 0 0 0 1 1 1 ~ ~ ~5 filtered normal wool 14"
+        );
+
+        let execute = Command::Execute {
+            subcommands: vec![
+                Execute::As("@a".to_string()),
+                Execute::At("@s".to_string()),
+                Execute::If(Condition::Block(Position::relative(0.0, -1.0, 0.0), "grass".into())),
+            ],
+            run: Some(Box::new(Command::Kill {
+                targets: "@e[type=item,r=4]".to_string(),
+            })),
+        };
+        assert_eq!(
+            execute.display(v).to_string(),
+            "execute @a ~ ~ ~ detect ~ ~-1 ~ grass -1 kill @e[type=item,r=4]"
+        );
+
+        let merge = Command::Data(Data::Merge {
+            target: DataTarget::Entity("@e[type=zombie]".to_string()),
+            nbt: Nbt::compound().with("NoAI", true),
+        });
+        assert_eq!(merge.display(v).to_string(), "entitydata @e[type=zombie] {NoAI:1b}");
+    }
+
+    #[test]
+    fn test_give() {
+        let give = |item: Item| Command::Give {
+            targets: "@p".to_string(),
+            item,
+        };
+
+        let sword = Item::new("diamond_sword")
+            .with_component("damage", 5)
+            .with_component("unbreakable", Nbt::compound());
+        assert_eq!(
+            give(sword.clone()).display(Version::V1_12).to_string(),
+            "give @p diamond_sword 1 5 {Unbreakable:1b}"
+        );
+        assert_eq!(
+            give(sword.clone()).display(Version::V1_20).to_string(),
+            "give @p diamond_sword{Damage:5,Unbreakable:1b}"
+        );
+        assert_eq!(
+            give(sword).to_string(),
+            "give @p diamond_sword[damage=5,unbreakable={}]"
+        );
+
+        let stone = Item::new("stone").with_count(64);
+        assert_eq!(
+            give(stone.clone()).display(Version::V1_12).to_string(),
+            "give @p stone 64"
+        );
+        assert_eq!(give(stone).to_string(), "give @p stone 64");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_legacy_data_get() {
+        let get = Command::Data(Data::Get {
+            target: DataTarget::Entity("@s".to_string()),
+            path: None,
+            scale: None,
+        });
+
+        get.display(Version::V1_12).to_string();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_legacy_at() {
+        let execute = Command::Execute {
+            subcommands: vec![Execute::At("@p".to_string())],
+            run: Some(Box::new(Command::Kill {
+                targets: "@e[type=item,r=4]".to_string(),
+            })),
+        };
+
+        execute.display(Version::V1_12).to_string();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_legacy_local_teleport() {
+        let tp = Command::Teleport {
+            targets: None,
+            pos: Position::local(0.0, 0.0, 1.0),
+            rotation: None,
+        };
+
+        tp.display(Version::V1_12).to_string();
+    }
+}
@@ -0,0 +1,258 @@
+//! Item arguments of commands and item stacks in entity data.
+//!
+//! Items are described with the components used from 1.20.5 on. Before that, the components
+//! which have one are written as their item NBT, see `Item::tag`.
+
+use std::fmt::{self, Display};
+
+use crate::util::Version;
+
+use super::syntax::{Syntax, Versioned, LATEST};
+use super::Nbt;
+
+/// An item stack, `diamond_sword[damage=5]` for example.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Item {
+    pub name: String,
+    pub count: u8,
+    /// The components set on the item and their SNBT values, `minecraft:` may be left out.
+    pub components: Vec<(String, Nbt)>,
+}
+
+impl Item {
+    pub fn new(name: &str) -> Self {
+        Item {
+            name: name.to_string(),
+            count: 1,
+            components: Vec::new(),
+        }
+    }
+
+    pub fn with_count(mut self, count: u8) -> Self {
+        self.count = count;
+        self
+    }
+
+    /// Sets the component `key`, replacing any previous value.
+    pub fn with_component<V: Into<Nbt>>(mut self, key: &str, value: V) -> Self {
+        let value = value.into();
+
+        match self.components.iter_mut().find(|(k, _)| k == key) {
+            Some(component) => component.1 = value,
+            None => self.components.push((key.to_string(), value)),
+        }
+
+        self
+    }
+
+    /// The item for `version`, see `Display`. Before 1.13 the count, damage and NBT are separate
+    /// arguments, see `Command::Give`.
+    pub fn display(&self, version: Version) -> impl Display + '_ {
+        Versioned(self, version)
+    }
+
+    fn component(&self, key: &str) -> Option<&Nbt> {
+        self.components.iter().find(|(k, _)| id(k) == key).map(|(_, v)| v)
+    }
+
+    /// The damage of the item, its data value before 1.13.
+    pub(super) fn damage(&self) -> i32 {
+        match self.component("damage") {
+            Some(Nbt::Int(damage)) => *damage,
+            Some(other) => panic!("not a damage value: {}", other),
+            None => 0,
+        }
+    }
+
+    /// The item NBT the components are written as before 1.20.5, `None` without components.
+    /// Before 1.13 the damage is left out, it is the data value.
+    ///
+    /// Panics for components without item NBT: only `custom_data`, `custom_name`, `damage`,
+    /// `enchantments` (from 1.13 on) and `unbreakable` have one.
+    pub fn tag(&self, version: Version) -> Option<Nbt> {
+        if self.components.is_empty() {
+            return None;
+        }
+
+        let mut tag = Nbt::compound();
+        for (key, value) in &self.components {
+            tag = match id(key) {
+                "custom_data" => match value {
+                    Nbt::Compound(entries) => entries.iter().fold(tag, |tag, (k, v)| tag.with(k, v.clone())),
+                    other => panic!("custom data is not a compound: {}", other),
+                },
+                "custom_name" => tag.with("display", Nbt::compound().with("Name", value.clone())),
+                "damage" if version < Version::V1_13 => tag,
+                "damage" => tag.with("Damage", value.clone()),
+                "enchantments" if version < Version::V1_13 => panic!("enchantments have numeric ids before 1.13"),
+                "enchantments" => tag.with("Enchantments", enchantments(value)),
+                "unbreakable" => tag.with("Unbreakable", true),
+                other => panic!("no item NBT for the component {} before 1.20.5", other),
+            };
+        }
+
+        Some(tag)
+    }
+
+    /// The item as stored in entity and block entity data, `{id:"stone",count:1}` for example.
+    /// Panics like `tag`.
+    pub fn to_nbt(&self, version: Version) -> Nbt {
+        let nbt = Nbt::compound().with("id", self.name.as_str());
+
+        if version >= Version::V1_20_5 {
+            let nbt = nbt.with("count", self.count as i32);
+
+            if self.components.is_empty() {
+                return nbt;
+            }
+
+            let components = self
+                .components
+                .iter()
+                .map(|(k, v)| (namespaced(k), v.clone()))
+                .collect();
+            return nbt.with("components", Nbt::Compound(components));
+        }
+
+        let mut nbt = nbt.with("Count", self.count as i8);
+        if version < Version::V1_13 {
+            nbt = nbt.with("Damage", self.damage() as i16);
+        }
+
+        match self.tag(version) {
+            Some(tag) if tag != Nbt::compound() => nbt.with("tag", tag),
+            _ => nbt,
+        }
+    }
+}
+
+impl From<&str> for Item {
+    /// A single `name`, without components.
+    fn from(name: &str) -> Self {
+        Item::new(name)
+    }
+}
+
+impl Display for Item {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, LATEST)
+    }
+}
+
+impl Syntax for Item {
+    fn write(&self, f: &mut fmt::Formatter<'_>, version: Version) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+
+        if version < Version::V1_13 {
+            return Ok(());
+        }
+
+        if version < Version::V1_20_5 {
+            return match self.tag(version) {
+                Some(tag) => write!(f, "{}", tag.display(version)),
+                None => Ok(()),
+            };
+        }
+
+        if !self.components.is_empty() {
+            let components: Vec<String> = self
+                .components
+                .iter()
+                .map(|(k, v)| format!("{}={}", k, v.display(version)))
+                .collect();
+
+            write!(f, "[{}]", components.join(","))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The component `key` without the `minecraft:` namespace.
+fn id(key: &str) -> &str {
+    key.strip_prefix("minecraft:").unwrap_or(key)
+}
+
+fn namespaced(key: &str) -> String {
+    if key.contains(':') {
+        key.to_string()
+    } else {
+        format!("minecraft:{}", key)
+    }
+}
+
+/// The `Enchantments` list of the `enchantments` component, `{levels:{sharpness:5}}` or
+/// `{sharpness:5}`.
+fn enchantments(component: &Nbt) -> Nbt {
+    let levels = match component.get("levels").unwrap_or(component) {
+        Nbt::Compound(levels) => levels,
+        other => panic!("not enchantment levels: {}", other),
+    };
+
+    let level = |v: &Nbt| match v {
+        Nbt::Int(v) => Nbt::Short(*v as i16),
+        other => panic!("not an enchantment level: {}", other),
+    };
+
+    Nbt::List(
+        levels
+            .iter()
+            .map(|(id, lvl)| Nbt::compound().with("id", namespaced(id)).with("lvl", level(lvl)))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use crate::cmd::Nbt;
+    use crate::util::Version;
+
+    use super::Item;
+
+    #[test]
+    fn test_item() {
+        let sword = Item::new("diamond_sword")
+            .with_component("damage", 5)
+            .with_component(
+                "enchantments",
+                Nbt::compound().with("levels", Nbt::compound().with("sharpness", 5)),
+            )
+            .with_component("custom_data", Nbt::compound().with("farm", true));
+
+        assert_eq!(
+            sword.to_string(),
+            "diamond_sword[damage=5,enchantments={levels:{sharpness:5}},custom_data={farm:1b}]"
+        );
+        assert_eq!(
+            sword.display(Version::V1_20).to_string(),
+            r#"diamond_sword{Damage:5,Enchantments:[{id:"minecraft:sharpness",lvl:5s}],farm:1b}"#
+        );
+
+        let stone = Item::new("stone").with_count(3);
+        assert_eq!(stone.to_string(), "stone");
+        assert_eq!(stone.to_nbt(Version::V1_21).to_string(), r#"{id:"stone",count:3}"#);
+        assert_eq!(stone.to_nbt(Version::V1_16).to_string(), r#"{id:"stone",Count:3b}"#);
+        assert_eq!(
+            stone.to_nbt(Version::V1_12).to_string(),
+            r#"{id:"stone",Count:3b,Damage:0s}"#
+        );
+
+        let named = Item::new("stick").with_component("custom_name", r#"{"text":"Wand"}"#);
+        assert_eq!(
+            named.to_nbt(Version::V1_21).to_string(),
+            r#"{id:"stick",count:1,components:{"minecraft:custom_name":'{"text":"Wand"}'}}"#
+        );
+        assert_eq!(
+            named.to_nbt(Version::V1_13).display(Version::V1_13).to_string(),
+            r#"{id:"stick",Count:1b,tag:{display:{Name:"{\"text\":\"Wand\"}"}}}"#
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_no_item_nbt() {
+        Item::new("stone")
+            .with_component("max_stack_size", 16)
+            .tag(Version::V1_20);
+    }
+}
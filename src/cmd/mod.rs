@@ -0,0 +1,440 @@
+//!
+//! This module abstracts away in game commands into callable functions.
+//!
+//! Commands are typed values, see `Command`. Their `Display` implementation writes the command
+//! text for the latest version, `Command::display` for older ones.
+//!
+
+mod block;
+mod command;
+mod item;
+mod nbt;
+mod pos;
+mod split;
+mod syntax;
+
+pub use block::BlockState;
+pub use command::{
+    CloneMask, CloneMode, Command, Condition, Data, DataTarget, Execute, FillMode, Scoreboard, SetBlockMode,
+};
+pub use item::Item;
+pub use nbt::{quote, Nbt};
+pub use pos::{Coord, Position, Rotation};
+pub use split::split;
+
+use std::fmt::Display;
+
+use crate::util::consts::MAX_COMMAND_LENGTH;
+use crate::util::{Dimension, Version};
+
+
+/**
+    * returns a setblock command given the block position and block, replacing the block there
+    **/ 
+pub fn setblock(p: impl Into<Position>, block: impl Into<BlockState>) -> Command {
+    Command::SetBlock {
+        pos: p.into(),
+        block: block.into(),
+        mode: SetBlockMode::Replace,
+    }
+}
+
+/**
+    * returns fill commands given two block positions and a block, replacing every block in
+    * between. Large volumes take several commands, see `split`
+    **/ 
+pub fn fill(p1: impl Into<Position>, p2: impl Into<Position>, block: impl Into<BlockState>) -> Vec<Command> {
+    split(Command::Fill {
+        from: p1.into(),
+        to: p2.into(),
+        block: block.into(),
+        mode: FillMode::Replace,
+    })
+}
+
+/**
+    * returns clone commands given two block positions and the lowest corner of the destination,
+    * copying every block in between. Large volumes take several commands, see `split`
+    **/ 
+pub fn clone(from: impl Into<Position>, to: impl Into<Position>, destination: impl Into<Position>) -> Vec<Command> {
+    split(Command::Clone {
+        from: from.into(),
+        to: to.into(),
+        destination: destination.into(),
+        mask: CloneMask::Replace,
+        mode: CloneMode::Normal,
+    })
+}
+
+
+/**
+    * returns a set of commands to generate 1 high walls given a center position, a sidelength, and
+    * a block type. Walls around a local center follow the direction the executor is facing
+    **/ 
+pub fn walls_2d(c: impl Into<Position>, sidelength: i32, block: impl Into<BlockState>) -> Vec<Command> {
+    let c = c.into();
+    let block = block.into();
+    let mut cmds = Vec::new();
+
+    // the corners, in order around the square
+    let l = (sidelength / 2) as f64;
+    let verts = [
+        c.offset(-l, 0.0, -l),
+        c.offset(-l, 0.0, l),
+        c.offset(l, 0.0, l),
+        c.offset(l, 0.0, -l),
+    ];
+
+    for i in 0..verts.len() {
+        cmds.extend(fill(verts[i], verts[(i + 1) % 4], block.clone()));
+    }
+
+    cmds
+}
+
+/**
+    * returns a set of commands to generate walls given a center position, a sidelength, a block
+    * type, and the dimension and version they are built in. The walls extend from the bottom to
+    * the top of the world, the `y` of the center is ignored. Panics if the center is local
+    **/ 
+pub fn walls_3d(
+    c: impl Into<Position>,
+    sidelength: i32,
+    block: impl Into<BlockState>,
+    dimension: Dimension,
+    version: Version,
+) -> Vec<Command> {
+    let c = c.into();
+    let block = block.into();
+    let mut cmds = Vec::new();
+
+    let column = columns(c, dimension, version);
+    let l = (sidelength / 2) as f64;
+    let verts = [(-l, -l), (-l, l), (l, l), (l, -l)];
+
+    for i in 0..verts.len() {
+        let ((x1, z1), (x2, z2)) = (verts[i], verts[(i + 1) % 4]);
+        let (bottom, top) = (column(x1, z1).0, column(x2, z2).1);
+
+        cmds.extend(fill(bottom, top, block.clone()));
+    }
+
+    cmds
+}
+
+/**
+    * returns a set of commands to replace every block with air given a center position, a
+    * sidelength, and the dimension and version to clear, from the bottom to the top of the world.
+    * The `y` of the center is ignored. Panics if the center is local
+    **/ 
+pub fn clear_3d(c: impl Into<Position>, sidelength: i32, dimension: Dimension, version: Version) -> Vec<Command> {
+    let column = columns(c.into(), dimension, version);
+    let l = (sidelength / 2) as f64;
+
+    fill(column(-l, -l).0, column(l, l).1, "air")
+}
+
+/**
+    * returns a function giving the bottom and top of the world at an offset from the given
+    * center. Panics if the center is local
+    **/ 
+fn columns(c: Position, dimension: Dimension, version: Version) -> impl Fn(f64, f64) -> (Position, Position) {
+    assert!(!c.is_local(), "columns spanning the world height need world axes");
+
+    move |dx: f64, dz: f64| {
+        let at = |y: i32| Position {
+            y: Coord::Absolute(y as f64),
+            ..c.offset(dx, 0.0, dz)
+        };
+
+        (at(dimension.min_y(version)), at(dimension.max_y(version) - 1))
+    }
+}
+
+/**
+    * returns a command block minecart running the given command
+    **/ 
+fn minecart(cmd: &str, version: Version) -> Nbt {
+    let id = if version < Version::V1_13 { "commandblock_minecart" } else { "command_block_minecart" };
+
+    Nbt::compound().with("id", id).with("Command", cmd)
+}
+
+/**
+    * returns a command summoning the given passengers on an activator rail, which runs them in order
+    **/ 
+fn loader(passengers: Vec<Nbt>, version: Version) -> Command {
+    let nbt = Nbt::compound().with("Time", 1);
+    let nbt = if version < Version::V1_13 {
+        nbt.with("Block", "minecraft:activator_rail")
+    } else {
+        nbt.with("BlockState", Nbt::compound().with("Name", "activator_rail"))
+    };
+
+    Command::Summon {
+        entity: "falling_block".to_string(),
+        pos: Some(Position::relative(0.0, 1.0, 0.0)),
+        nbt: Some(nbt.with("Passengers", passengers)),
+    }
+}
+
+/**
+    * returns the length of the given text as counted by the game
+    **/ 
+fn length(s: &impl Display) -> usize {
+    s.to_string().encode_utf16().count()
+}
+
+/**
+    * returns as few commands as possible to execute every command in the given list in order, each
+    * short enough for a command block. Commands are written for the given version, see
+    * `Command::display`. Panics if a single command is too long to fit
+    **/ 
+pub fn chain(cmds: Vec<Command>, version: Version) -> Vec<Command> {
+    // one final command block removes all previous command block minecarts in the chain
+    let kill = if version < Version::V1_13 {
+        minecart("kill @e[type=commandblock_minecart,r=1]", version)
+    } else {
+        minecart("kill @e[type=minecraft:command_block_minecart,distance=..1]", version)
+    };
+    let base = length(&loader(vec![kill.clone()], version).display(version));
+
+    let mut loaders = Vec::new();
+    let mut passengers = Vec::new();
+    let mut len = base;
+
+    for cmd in cmds {
+        let passenger = minecart(&cmd.display(version).to_string(), version);
+        let add = length(&passenger.display(version)) + 1;
+
+        assert!(base + add <= MAX_COMMAND_LENGTH, "command too long to chain: {}", cmd);
+
+        if len + add > MAX_COMMAND_LENGTH {
+            passengers.push(kill.clone());
+            loaders.push(loader(std::mem::take(&mut passengers), version));
+            len = base;
+        }
+
+        passengers.push(passenger);
+        len += add;
+    }
+
+    if !passengers.is_empty() {
+        passengers.push(kill);
+        loaders.push(loader(passengers, version));
+    }
+
+    loaders
+}
+
+/**
+    * returns the setblock commands placing a chain of command blocks running every command in the
+    * given list in order, starting at the given position and going towards `facing`. The first block
+    * is impulse, the others chain, and all are always active. The setblocks are returned in the order
+    * they must run, see `chain` to run them from a single command block. Commands are written for the
+    * given version. Panics if the start is local or `facing` is not a direction
+    **/ 
+pub fn chain_blocks(
+    cmds: Vec<Command>,
+    start: impl Into<Position>,
+    facing: &str,
+    version: Version,
+) -> Vec<Command> {
+    let start = start.into();
+    assert!(!start.is_local(), "command blocks are placed along world axes");
+
+    let (dx, dy, dz) = match facing {
+        "east" => (1.0, 0.0, 0.0),
+        "west" => (-1.0, 0.0, 0.0),
+        "up" => (0.0, 1.0, 0.0),
+        "down" => (0.0, -1.0, 0.0),
+        "south" => (0.0, 0.0, 1.0),
+        "north" => (0.0, 0.0, -1.0),
+        _ => panic!("not a direction: {}", facing),
+    };
+
+    // the impulse block runs as soon as it is placed, so it comes last, once the chain is complete
+    cmds.iter()
+        .enumerate()
+        .rev()
+        .map(|(i, cmd)| {
+            let kind = if i == 0 { "command_block" } else { "chain_command_block" };
+            let i = i as f64;
+
+            let block = BlockState::new(kind)
+                .with_property("facing", facing)
+                .with_nbt(Nbt::compound().with("Command", cmd.display(version).to_string()).with("auto", true));
+
+            setblock(start.offset(dx * i, dy * i, dz * i), block)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::util::consts::{MAX_COMMAND_LENGTH, MAX_FILL_VOLUME};
+    use crate::util::{Dimension, Point3D, Version};
+
+    use super::{chain, chain_blocks, clear_3d, clone, fill, setblock, walls_2d, walls_3d, Command, Nbt, Position};
+
+    #[test]
+    fn test_block_helpers() {
+        let p = Point3D { x: 1, y: -60, z: -2 };
+
+        assert_eq!(setblock(p, "stone").to_string(), "setblock 1 -60 -2 stone");
+        assert_eq!(fill(p, Point3D { x: 3, y: -59, z: 0 }, "air")[0].to_string(), "fill 1 -60 -2 3 -59 0 air");
+
+        // 64 * 32 * 32 blocks, twice the limit
+        let cmds = fill(Position::relative(0.0, 0.0, 0.0), Position::relative(63.0, 31.0, 31.0), "air");
+        assert_eq!(cmds.len(), 2);
+        assert_eq!(cmds[1].to_string(), "fill ~ ~ ~16 ~63 ~31 ~31 air");
+
+        let cmds = clone(p, Point3D { x: 64, y: 3, z: 13 }, Position::relative(0.0, 0.0, 0.0));
+        assert_eq!(cmds.len(), 2);
+        assert_eq!(cmds[1].to_string(), "clone 1 -60 6 64 3 13 ~ ~ ~8");
+
+        assert_eq!(setblock(Position::relative(0.0, 1.0, 0.0), "stone").to_string(), "setblock ~ ~1 ~ stone");
+    }
+
+    #[test]
+    fn test_walls() {
+        let walls = |cmds: Vec<super::Command>| cmds.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+        let p = Point3D { x: 1, y: -60, z: -2 };
+
+        assert_eq!(
+            walls(walls_2d(p, 4, "glass")),
+            [
+                "fill -1 -60 -4 -1 -60 0 glass",
+                "fill -1 -60 0 3 -60 0 glass",
+                "fill 3 -60 0 3 -60 -4 glass",
+                "fill 3 -60 -4 -1 -60 -4 glass",
+            ]
+        );
+
+        assert_eq!(walls(walls_2d(Position::local(0.0, 0.0, 3.0), 2, "glass"))[0], "fill ^-1 ^ ^2 ^-1 ^ ^4 glass");
+        assert_eq!(
+            walls(walls_3d(Position::here(), 2, "glass", Dimension::Overworld, Version::V1_18))[1],
+            "fill ~-1 -64 ~1 ~1 319 ~1 glass"
+        );
+        assert_eq!(
+            walls(walls_3d(Position::here(), 2, "glass", Dimension::Overworld, Version::V1_16))[1],
+            "fill ~-1 0 ~1 ~1 255 ~1 glass"
+        );
+        assert_eq!(
+            walls(walls_3d(p, 2, "glass", Dimension::Nether, Version::V1_21))[0],
+            "fill 0 0 -3 0 255 -1 glass"
+        );
+
+        // 127 blocks long walls are over the limit in the overworld from 1.18 on, not in the end
+        assert_eq!(walls_3d(p, 126, "glass", Dimension::Overworld, Version::V1_21).len(), 8);
+        assert_eq!(walls_3d(p, 126, "glass", Dimension::Overworld, Version::V1_16).len(), 4);
+        assert_eq!(walls_3d(p, 126, "glass", Dimension::End, Version::V1_21).len(), 4);
+    }
+
+    #[test]
+    fn test_clear() {
+        let cmds = clear_3d(Point3D { x: 0, y: 100, z: 0 }, 16, Dimension::Overworld, Version::V1_21);
+        let origin = Point3D { x: 0.0, y: 0.0, z: 0.0 };
+        let volume = |c: &Command| match c {
+            Command::Fill { from, to, .. } => {
+                let (a, b) = (from.resolve_block(origin, (0.0, 0.0)), to.resolve_block(origin, (0.0, 0.0)));
+                let len = |a: i32, b: i32| (a - b).abs() as i64 + 1;
+
+                len(a.x, b.x) * len(a.y, b.y) * len(a.z, b.z)
+            }
+            _ => unreachable!(),
+        };
+
+        assert_eq!(cmds.len(), 4);
+        assert_eq!(cmds[0].to_string(), "fill -8 -64 -8 8 319 -5 air");
+        assert!(cmds.iter().all(|c| volume(c) <= MAX_FILL_VOLUME));
+        assert_eq!(cmds.iter().map(volume).sum::<i64>(), 17 * 17 * 384);
+
+        let legacy = clear_3d(Point3D { x: 0, y: 100, z: 0 }, 16, Dimension::Overworld, Version::V1_16);
+        assert!(legacy[0].to_string().starts_with("fill -8 0 -8 8 255 "));
+        assert_eq!(legacy.iter().map(volume).sum::<i64>(), 17 * 17 * 256);
+    }
+
+    #[test]
+    fn test_chain() {
+        let p = Point3D { x: 1, y: -60, z: -2 };
+
+        assert!(chain(Vec::new(), Version::V1_21).is_empty());
+        assert_eq!(
+            chain(vec![setblock(p, "stone"), Command::Raw(r#"say "hi""#.to_string())], Version::V1_21)[0].to_string(),
+            concat!(
+                r#"summon falling_block ~ ~1 ~ {Time:1,BlockState:{Name:"activator_rail"},Passengers:["#,
+                r#"{id:"command_block_minecart",Command:"setblock 1 -60 -2 stone"},"#,
+                r#"{id:"command_block_minecart",Command:'say "hi"'},"#,
+                r#"{id:"command_block_minecart",Command:"kill @e[type=minecraft:command_block_minecart,distance=..1]"}]}"#,
+            )
+        );
+
+        // 1000 commands of about 100 characters don't fit in one command block
+        let say = |i: usize| Command::Raw(format!("say {:0100}", i));
+        let loaders: Vec<String> = chain((0..1000).map(say).collect(), Version::V1_21)
+            .iter()
+            .map(|c| c.to_string())
+            .collect();
+
+        assert_eq!(loaders.len(), 5);
+        assert!(loaders.iter().all(|l| l.len() <= MAX_COMMAND_LENGTH));
+        assert!(loaders.iter().all(|l| l.ends_with("distance=..1]\"}]}")));
+
+        let all = loaders.concat();
+        let positions: Vec<usize> = (0..1000).map(|i| all.find(&say(i).to_string()).unwrap()).collect();
+        assert!(positions.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_chain_blocks() {
+        let cmds = vec![Command::Raw("say 1".to_string()), Command::Raw("say 2".to_string())];
+        let setblocks = chain_blocks(cmds.clone(), Point3D { x: 0, y: 64, z: 0 }, "south", Version::V1_21);
+        let setblocks: Vec<String> = setblocks.iter().map(ToString::to_string).collect();
+
+        assert_eq!(
+            setblocks,
+            [
+                r#"setblock 0 64 1 chain_command_block[facing=south]{Command:"say 2",auto:1b}"#,
+                r#"setblock 0 64 0 command_block[facing=south]{Command:"say 1",auto:1b}"#,
+            ]
+        );
+
+        let legacy = chain(
+            chain_blocks(cmds, Point3D { x: 0, y: 64, z: 0 }, "south", Version::V1_12),
+            Version::V1_12,
+        );
+        assert_eq!(
+            legacy[0].display(Version::V1_12).to_string(),
+            concat!(
+                r#"summon falling_block ~ ~1 ~ {Time:1,Block:"minecraft:activator_rail",Passengers:["#,
+                r#"{id:"commandblock_minecart",Command:"setblock 0 64 1 chain_command_block facing=south replace {Command:\"say 2\",auto:1b}"},"#,
+                r#"{id:"commandblock_minecart",Command:"setblock 0 64 0 command_block facing=south replace {Command:\"say 1\",auto:1b}"},"#,
+                r#"{id:"commandblock_minecart",Command:"kill @e[type=commandblock_minecart,r=1]"}]}"#,
+            )
+        );
+    }
+
+    #[test]
+    fn test_nested_chain() {
+        let say = Command::Raw(r#"tellraw @a {"text":"it's \\o/"}"#.to_string());
+        let inner = chain(vec![say.clone()], Version::V1_21).remove(0);
+        let outer = chain(vec![inner.clone()], Version::V1_21).remove(0);
+
+        // the command run by the first minecart of a loader
+        let first = |loader: &Command| match loader {
+            Command::Summon { nbt: Some(nbt), .. } => {
+                let nbt = Nbt::parse(&nbt.to_string()).unwrap();
+
+                match nbt.get("Passengers") {
+                    Some(Nbt::List(passengers)) => passengers[0].get("Command").cloned().unwrap(),
+                    _ => unreachable!(),
+                }
+            }
+            _ => unreachable!(),
+        };
+
+        assert_eq!(first(&outer), Nbt::String(inner.to_string()));
+        assert_eq!(first(&inner), Nbt::String(say.to_string()));
+    }
+}
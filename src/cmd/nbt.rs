@@ -0,0 +1,524 @@
+//! NBT values and their text form (SNBT), as written in commands.
+
+use std::fmt::{self, Display};
+
+use crate::util::Version;
+
+use super::syntax::{Syntax, Versioned, LATEST};
+
+/// An NBT value. Compounds keep the order their entries were added in.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Nbt {
+    Byte(i8),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    String(String),
+    List(Vec<Nbt>),
+    Compound(Vec<(String, Nbt)>),
+    ByteArray(Vec<i8>),
+    IntArray(Vec<i32>),
+    LongArray(Vec<i64>),
+}
+
+impl Nbt {
+    /// An empty compound, see `with`.
+    pub fn compound() -> Self {
+        Nbt::Compound(Vec::new())
+    }
+
+    /// Sets the entry `key` of a compound, replacing any previous value. Panics if `self` is not a
+    /// compound.
+    pub fn with<V: Into<Nbt>>(mut self, key: &str, value: V) -> Self {
+        let entries = match &mut self {
+            Nbt::Compound(entries) => entries,
+            other => panic!("not a compound: {}", other),
+        };
+
+        let value = value.into();
+        match entries.iter_mut().find(|(k, _)| k == key) {
+            Some(entry) => entry.1 = value,
+            None => entries.push((key.to_string(), value)),
+        }
+
+        self
+    }
+
+    /// Returns the entry `key` of a compound, `None` if there is none or `self` is not a compound.
+    pub fn get(&self, key: &str) -> Option<&Nbt> {
+        match self {
+            Nbt::Compound(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+}
+
+impl From<bool> for Nbt {
+    fn from(v: bool) -> Self {
+        Nbt::Byte(v as i8)
+    }
+}
+
+impl From<i8> for Nbt {
+    fn from(v: i8) -> Self {
+        Nbt::Byte(v)
+    }
+}
+
+impl From<i16> for Nbt {
+    fn from(v: i16) -> Self {
+        Nbt::Short(v)
+    }
+}
+
+impl From<i32> for Nbt {
+    fn from(v: i32) -> Self {
+        Nbt::Int(v)
+    }
+}
+
+impl From<i64> for Nbt {
+    fn from(v: i64) -> Self {
+        Nbt::Long(v)
+    }
+}
+
+impl From<f32> for Nbt {
+    fn from(v: f32) -> Self {
+        Nbt::Float(v)
+    }
+}
+
+impl From<f64> for Nbt {
+    fn from(v: f64) -> Self {
+        Nbt::Double(v)
+    }
+}
+
+impl From<&str> for Nbt {
+    fn from(v: &str) -> Self {
+        Nbt::String(v.to_string())
+    }
+}
+
+impl From<String> for Nbt {
+    fn from(v: String) -> Self {
+        Nbt::String(v)
+    }
+}
+
+impl From<Vec<Nbt>> for Nbt {
+    fn from(v: Vec<Nbt>) -> Self {
+        Nbt::List(v)
+    }
+}
+
+impl Nbt {
+    /// The SNBT of the value for `version`, see `Display`.
+    pub fn display(&self, version: Version) -> impl Display + '_ {
+        Versioned(self, version)
+    }
+}
+
+impl Display for Nbt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, LATEST)
+    }
+}
+
+impl Syntax for Nbt {
+    fn write(&self, f: &mut fmt::Formatter<'_>, version: Version) -> fmt::Result {
+        // strings can be single quoted from 1.14 on
+        let quote = |s: &str| quote_with(s, version >= Version::V1_14);
+
+        match self {
+            Nbt::Byte(v) => write!(f, "{}b", v),
+            Nbt::Short(v) => write!(f, "{}s", v),
+            Nbt::Int(v) => write!(f, "{}", v),
+            Nbt::Long(v) => write!(f, "{}L", v),
+            Nbt::Float(v) => write!(f, "{}f", v),
+            Nbt::Double(v) => write!(f, "{}d", v),
+            Nbt::String(v) => write!(f, "{}", quote(v)),
+            Nbt::List(values) => write_list(f, "", &values.iter().map(|v| v.display(version)).collect::<Vec<_>>()),
+            Nbt::Compound(entries) => {
+                write!(f, "{{")?;
+
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+
+                    if is_unquoted(key) {
+                        write!(f, "{}:{}", key, value.display(version))?;
+                    } else {
+                        write!(f, "{}:{}", quote(key), value.display(version))?;
+                    }
+                }
+
+                write!(f, "}}")
+            }
+            Nbt::ByteArray(values) => {
+                write_list(f, "B;", &values.iter().map(|v| format!("{}b", v)).collect::<Vec<_>>())
+            }
+            Nbt::IntArray(values) => write_list(f, "I;", values),
+            Nbt::LongArray(values) => {
+                write_list(f, "L;", &values.iter().map(|v| format!("{}L", v)).collect::<Vec<_>>())
+            }
+        }
+    }
+}
+
+fn write_list<T: Display>(f: &mut fmt::Formatter<'_>, prefix: &str, values: &[T]) -> fmt::Result {
+    write!(f, "[{}", prefix)?;
+
+    for (i, value) in values.iter().enumerate() {
+        if i > 0 {
+            write!(f, ",")?;
+        }
+
+        write!(f, "{}", value)?;
+    }
+
+    write!(f, "]")
+}
+
+/// Whether `s` can be written without quotes, as a compound key.
+fn is_unquoted(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric() || "_-.+".contains(c))
+}
+
+/// Wraps `s` in quotes, escaping backslashes and the quotes used. Double quotes are used unless
+/// `s` contains some and no single quotes, so that each level of nesting adds as few escapes as
+/// possible. The game reads single quotes from 1.14 on, see `Nbt::display`, and `Nbt::parse` for
+/// the reverse.
+pub fn quote(s: &str) -> String {
+    quote_with(s, true)
+}
+
+/// `quote`, only using double quotes unless `single` is set.
+fn quote_with(s: &str, single: bool) -> String {
+    let q = if single && s.contains('"') && !s.contains('\'') {
+        '\''
+    } else {
+        '"'
+    };
+
+    let mut res = String::with_capacity(s.len() + 2);
+    res.push(q);
+
+    for c in s.chars() {
+        if c == '\\' || c == q {
+            res.push('\\');
+        }
+
+        res.push(c);
+    }
+
+    res.push(q);
+    res
+}
+
+impl Nbt {
+    /// Reads the SNBT `s`, the way the game does. `None` if it is not valid.
+    ///
+    /// Unquoted values are typed from their suffix, numbers that don't fit their type and other
+    /// words are strings. `true` and `false` are bytes.
+    pub fn parse(s: &str) -> Option<Nbt> {
+        let mut reader = Reader { s, pos: 0 };
+
+        let value = reader.value()?;
+        reader.skip_whitespace();
+
+        if reader.pos == s.len() {
+            Some(value)
+        } else {
+            None
+        }
+    }
+}
+
+/// A position in an SNBT string being parsed.
+struct Reader<'a> {
+    s: &'a str,
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn peek(&self) -> Option<char> {
+        self.s[self.pos..].chars().next()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.next();
+        }
+    }
+
+    /// Skips whitespace, then `c` if it comes next.
+    fn accept(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+
+        if self.peek() == Some(c) {
+            self.next();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Option<()> {
+        if self.accept(c) {
+            Some(())
+        } else {
+            None
+        }
+    }
+
+    fn value(&mut self) -> Option<Nbt> {
+        self.skip_whitespace();
+
+        match self.peek()? {
+            '{' => self.compound(),
+            '[' => self.list(),
+            '"' | '\'' => self.quoted().map(Nbt::String),
+            _ => self.unquoted().map(|s| typed(&s)),
+        }
+    }
+
+    fn compound(&mut self) -> Option<Nbt> {
+        self.expect('{')?;
+        let mut entries: Vec<(String, Nbt)> = Vec::new();
+
+        if self.accept('}') {
+            return Some(Nbt::Compound(entries));
+        }
+
+        loop {
+            self.skip_whitespace();
+            let key = match self.peek()? {
+                '"' | '\'' => self.quoted()?,
+                _ => self.unquoted()?,
+            };
+
+            self.expect(':')?;
+            let value = self.value()?;
+
+            match entries.iter_mut().find(|(k, _)| *k == key) {
+                Some(entry) => entry.1 = value,
+                None => entries.push((key, value)),
+            }
+
+            if !self.accept(',') {
+                self.expect('}')?;
+                return Some(Nbt::Compound(entries));
+            }
+        }
+    }
+
+    fn list(&mut self) -> Option<Nbt> {
+        self.expect('[')?;
+
+        // typed arrays, `[I;1,2]`
+        let rest = &self.s[self.pos..];
+        let array = ["B;", "I;", "L;"].iter().find(|prefix| rest.starts_with(*prefix));
+        if let Some(prefix) = array {
+            self.pos += prefix.len();
+        }
+
+        let mut values = Vec::new();
+        if !self.accept(']') {
+            loop {
+                values.push(self.value()?);
+
+                if !self.accept(',') {
+                    self.expect(']')?;
+                    break;
+                }
+            }
+        }
+
+        let numbers = |f: fn(&Nbt) -> Option<i64>| values.iter().map(f).collect::<Option<Vec<i64>>>();
+        match array.map(|prefix| &prefix[..1]) {
+            None => Some(Nbt::List(values)),
+            Some("B") => numbers(|v| match v {
+                Nbt::Byte(v) => Some(*v as i64),
+                _ => None,
+            })
+            .map(|v| Nbt::ByteArray(v.into_iter().map(|v| v as i8).collect())),
+            Some("I") => numbers(|v| match v {
+                Nbt::Int(v) => Some(*v as i64),
+                _ => None,
+            })
+            .map(|v| Nbt::IntArray(v.into_iter().map(|v| v as i32).collect())),
+            _ => numbers(|v| match v {
+                Nbt::Long(v) => Some(*v),
+                _ => None,
+            })
+            .map(Nbt::LongArray),
+        }
+    }
+
+    /// A string in single or double quotes, where backslashes escape the quote and themselves.
+    fn quoted(&mut self) -> Option<String> {
+        let q = self.next()?;
+        let mut res = String::new();
+
+        loop {
+            match self.next()? {
+                '\\' => match self.next()? {
+                    c if c == q || c == '\\' => res.push(c),
+                    _ => return None,
+                },
+                c if c == q => return Some(res),
+                c => res.push(c),
+            }
+        }
+    }
+
+    fn unquoted(&mut self) -> Option<String> {
+        let start = self.pos;
+
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || "_-.+".contains(c))
+        {
+            self.next();
+        }
+
+        if self.pos == start {
+            None
+        } else {
+            Some(self.s[start..self.pos].to_string())
+        }
+    }
+}
+
+/// The value of an unquoted word, see `Nbt::parse`.
+fn typed(s: &str) -> Nbt {
+    match s {
+        "true" => return Nbt::Byte(1),
+        "false" => return Nbt::Byte(0),
+        _ => {}
+    }
+
+    // only numbers start like numbers, `inf` or `NaN` are words
+    if !s.starts_with(|c: char| c.is_ascii_digit() || "+-.".contains(c)) {
+        return Nbt::String(s.to_string());
+    }
+
+    let (value, suffix) = s.split_at(s.len() - 1);
+    let number = match suffix {
+        "b" | "B" => value.parse().ok().map(Nbt::Byte),
+        "s" | "S" => value.parse().ok().map(Nbt::Short),
+        "l" | "L" => value.parse().ok().map(Nbt::Long),
+        "f" | "F" => value.parse().ok().map(Nbt::Float),
+        "d" | "D" => value.parse().ok().map(Nbt::Double),
+        _ if s.contains(['.', 'e', 'E']) => s.parse().ok().map(Nbt::Double),
+        _ => s.parse().ok().map(Nbt::Int),
+    };
+
+    number.unwrap_or_else(|| Nbt::String(s.to_string()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{quote, Nbt};
+
+    #[test]
+    fn test_snbt() {
+        let nbt = Nbt::compound()
+            .with("Time", 1)
+            .with("NoGravity", true)
+            .with("Motion", vec![Nbt::Double(0.0), Nbt::Double(0.5), Nbt::Double(-1.0)])
+            .with("BlockState", Nbt::compound().with("Name", "minecraft:activator_rail"))
+            .with("custom key", Nbt::Float(1.5))
+            .with("Ids", Nbt::IntArray(vec![1, -2]))
+            .with("Time", 2);
+
+        assert_eq!(
+            nbt.to_string(),
+            r#"{Time:2,NoGravity:1b,Motion:[0d,0.5d,-1d],BlockState:{Name:"minecraft:activator_rail"},"custom key":1.5f,Ids:[I;1,-2]}"#
+        );
+        assert_eq!(nbt.get("Time"), Some(&Nbt::Int(2)));
+        assert_eq!(nbt.get("Missing"), None);
+
+        assert_eq!(Nbt::LongArray(vec![3]).to_string(), "[L;3L]");
+        assert_eq!(Nbt::ByteArray(vec![]).to_string(), "[B;]");
+    }
+
+    #[test]
+    fn test_quote() {
+        assert_eq!(quote("stone"), r#""stone""#);
+        assert_eq!(quote(r#"say "hi""#), r#"'say "hi"'"#);
+        assert_eq!(quote(r#"say "it's""#), r#""say \"it's\"""#);
+        assert_eq!(quote(r"a\b"), r#""a\\b""#);
+        assert_eq!(quote(r#"\""#), r#"'\\"'"#);
+    }
+
+    #[test]
+    fn test_parse() {
+        let nbt = Nbt::compound()
+            .with("Byte", Nbt::Byte(-1))
+            .with("Short", Nbt::Short(300))
+            .with("Int", 5)
+            .with("Long", Nbt::Long(1 << 40))
+            .with("Float", Nbt::Float(0.25))
+            .with("Double", Nbt::Double(-1e-3))
+            .with("Number", "5")
+            .with("Word", "true")
+            .with("Quotes", r#"'"\"#)
+            .with("a key", Nbt::List(vec![Nbt::compound(), Nbt::List(vec![])]))
+            .with(
+                "Arrays",
+                vec![Nbt::ByteArray(vec![1]), Nbt::IntArray(vec![]), Nbt::LongArray(vec![-2])],
+            );
+
+        assert_eq!(Nbt::parse(&nbt.to_string()), Some(nbt));
+
+        assert_eq!(
+            Nbt::parse(r#" { a : 1b , 'b' : [ 1.5 , 2.0d ] , c : "x" , d : true , e : 300b , f : -} "#),
+            Some(
+                Nbt::compound()
+                    .with("a", Nbt::Byte(1))
+                    .with("b", vec![Nbt::Double(1.5), Nbt::Double(2.0)])
+                    .with("c", "x")
+                    .with("d", Nbt::Byte(1))
+                    .with("e", "300b")
+                    .with("f", "-")
+            )
+        );
+
+        assert_eq!(Nbt::parse("{a:1"), None);
+        assert_eq!(Nbt::parse("[I;1b]"), None);
+        assert_eq!(Nbt::parse(r#""a\b""#), None);
+        assert_eq!(Nbt::parse("a b"), None);
+    }
+
+    #[test]
+    fn test_nested_quotes() {
+        // each level quotes the text of the one below
+        let mut s = r#"say "it's" \o/"#.to_string();
+        let mut levels = vec![s.clone()];
+
+        for _ in 0..5 {
+            s = Nbt::compound().with("Command", s).to_string();
+            levels.push(s.clone());
+        }
+
+        while levels.len() > 1 {
+            let outer = levels.pop().unwrap();
+            let inner = Nbt::parse(&outer).unwrap().get("Command").cloned();
+
+            assert_eq!(inner, Some(Nbt::String(levels.last().unwrap().clone())));
+        }
+    }
+}
@@ -0,0 +1,383 @@
+//! Positions and rotations as written in commands: absolute, relative to the executor (`~`) or
+//! local to where it is facing (`^`).
+//!
+//! Absolute and relative axes can be mixed freely, local axes can't be mixed with the others.
+
+use std::fmt::{self, Display};
+
+use crate::util::{BlockPoint, Point3D, Version};
+
+use super::syntax::{Syntax, Versioned, LATEST};
+
+/// A single axis of a position or rotation.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Coord {
+    /// A world coordinate, `64`.
+    Absolute(f64),
+
+    /// An offset from the executor along the world axis, `~2`.
+    Relative(f64),
+
+    /// An offset from the executor along its left, up or forward axis, `^2`.
+    Local(f64),
+}
+
+impl Coord {
+    /// Moves the coordinate by `d` along its axis, keeping its kind.
+    pub fn offset(self, d: f64) -> Self {
+        match self {
+            Coord::Absolute(v) => Coord::Absolute(v + d),
+            Coord::Relative(v) => Coord::Relative(v + d),
+            Coord::Local(v) => Coord::Local(v + d),
+        }
+    }
+
+    pub fn is_local(&self) -> bool {
+        matches!(self, Coord::Local(_))
+    }
+
+    /// The value of the coordinate along a world axis, where the executor is at `origin`. Panics if
+    /// the coordinate is local.
+    pub fn resolve(&self, origin: f64) -> f64 {
+        match *self {
+            Coord::Absolute(v) => v,
+            Coord::Relative(d) => origin + d,
+            Coord::Local(_) => panic!("local coordinates need all three axes"),
+        }
+    }
+}
+
+impl Display for Coord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (prefix, v) = match *self {
+            Coord::Absolute(v) => return write!(f, "{}", v),
+            Coord::Relative(v) => ("~", v),
+            Coord::Local(v) => ("^", v),
+        };
+
+        if v == 0.0 {
+            write!(f, "{}", prefix)
+        } else {
+            write!(f, "{}{}", prefix, v)
+        }
+    }
+}
+
+/// A position argument, one `Coord` per axis. Local axes are left, up and forward instead of `x`,
+/// `y` and `z`.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Position {
+    pub x: Coord,
+    pub y: Coord,
+    pub z: Coord,
+}
+
+impl Position {
+    /// Panics if local axes are mixed with absolute or relative ones, which the game refuses.
+    pub fn new(x: Coord, y: Coord, z: Coord) -> Self {
+        let pos = Position { x, y, z };
+        pos.check();
+
+        pos
+    }
+
+    pub fn absolute(x: f64, y: f64, z: f64) -> Self {
+        Position {
+            x: Coord::Absolute(x),
+            y: Coord::Absolute(y),
+            z: Coord::Absolute(z),
+        }
+    }
+
+    /// The offset (`dx`, `dy`, `dz`) from the executor, `~dx ~dy ~dz`.
+    pub fn relative(dx: f64, dy: f64, dz: f64) -> Self {
+        Position {
+            x: Coord::Relative(dx),
+            y: Coord::Relative(dy),
+            z: Coord::Relative(dz),
+        }
+    }
+
+    /// The offset from the executor along the direction it is facing, `^left ^up ^forward`.
+    pub fn local(left: f64, up: f64, forward: f64) -> Self {
+        Position {
+            x: Coord::Local(left),
+            y: Coord::Local(up),
+            z: Coord::Local(forward),
+        }
+    }
+
+    /// The position of the executor, `~ ~ ~`.
+    pub fn here() -> Self {
+        Position::relative(0.0, 0.0, 0.0)
+    }
+
+    /// The position for `version`, see `Display`. Panics if it is local before 1.13, which added
+    /// local axes.
+    pub fn display(&self, version: Version) -> impl Display + '_ {
+        Versioned(self, version)
+    }
+
+    /// Whether the axes are local, left, up and forward.
+    pub fn is_local(&self) -> bool {
+        self.check();
+
+        self.x.is_local()
+    }
+
+    /// Moves the position along each of its axes, which are left, up and forward for local
+    /// positions.
+    pub fn offset(&self, dx: f64, dy: f64, dz: f64) -> Self {
+        Position {
+            x: self.x.offset(dx),
+            y: self.y.offset(dy),
+            z: self.z.offset(dz),
+        }
+    }
+
+    /// The world position this resolves to for an executor at `origin`, facing `rotation` (yaw and
+    /// pitch in degrees). Panics if local axes are mixed with other ones.
+    ///
+    /// The game computes local positions with a lookup table of sines, the result may differ in
+    /// the last decimals.
+    pub fn resolve(&self, origin: Point3D<f64>, rotation: (f64, f64)) -> Point3D<f64> {
+        if !self.is_local() {
+            return Point3D {
+                x: self.x.resolve(origin.x),
+                y: self.y.resolve(origin.y),
+                z: self.z.resolve(origin.z),
+            };
+        }
+
+        let local = |c: Coord| match c {
+            Coord::Local(v) => v,
+            _ => unreachable!(),
+        };
+        let (left, up, forward) = (local(self.x), local(self.y), local(self.z));
+
+        // see `LocalCoordinates.getPosition`
+        let (yaw, pitch) = ((rotation.0 + 90.0).to_radians(), rotation.1.to_radians());
+        let (up_pitch, pitch) = (90f64.to_radians() - pitch, -pitch);
+
+        let f = (yaw.cos() * pitch.cos(), pitch.sin(), yaw.sin() * pitch.cos());
+        let u = (yaw.cos() * up_pitch.cos(), up_pitch.sin(), yaw.sin() * up_pitch.cos());
+
+        // forward cross up, negated
+        let l = (
+            -(f.1 * u.2 - f.2 * u.1),
+            -(f.2 * u.0 - f.0 * u.2),
+            -(f.0 * u.1 - f.1 * u.0),
+        );
+
+        Point3D {
+            x: origin.x + f.0 * forward + u.0 * up + l.0 * left,
+            y: origin.y + f.1 * forward + u.1 * up + l.1 * left,
+            z: origin.z + f.2 * forward + u.2 * up + l.2 * left,
+        }
+    }
+
+    /// The block holding the resolved position, see `resolve`.
+    pub fn resolve_block(&self, origin: Point3D<f64>, rotation: (f64, f64)) -> BlockPoint {
+        let p = self.resolve(origin, rotation);
+
+        Point3D {
+            x: p.x.floor() as i32,
+            y: p.y.floor() as i32,
+            z: p.z.floor() as i32,
+        }
+    }
+
+    fn check(&self) {
+        let local = [self.x, self.y, self.z].iter().filter(|c| c.is_local()).count();
+        assert!(
+            local == 0 || local == 3,
+            "local axes can't be mixed with other ones: {}",
+            self
+        );
+    }
+}
+
+impl From<BlockPoint> for Position {
+    fn from(p: BlockPoint) -> Self {
+        Position::absolute(p.x as f64, p.y as f64, p.z as f64)
+    }
+}
+
+impl From<&BlockPoint> for Position {
+    fn from(p: &BlockPoint) -> Self {
+        Position::from(*p)
+    }
+}
+
+impl From<Point3D<f64>> for Position {
+    fn from(p: Point3D<f64>) -> Self {
+        Position::absolute(p.x, p.y, p.z)
+    }
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, LATEST)
+    }
+}
+
+impl Syntax for Position {
+    fn write(&self, f: &mut fmt::Formatter<'_>, version: Version) -> fmt::Result {
+        assert!(
+            version >= Version::V1_13 || !self.is_local(),
+            "no local positions before 1.13: {} {} {}",
+            self.x,
+            self.y,
+            self.z
+        );
+
+        write!(f, "{} {} {}", self.x, self.y, self.z)
+    }
+}
+
+/// A rotation argument in degrees, `yaw pitch`. Either axis may be relative, but not local.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Rotation {
+    pub yaw: Coord,
+    pub pitch: Coord,
+}
+
+impl Rotation {
+    pub fn absolute(yaw: f64, pitch: f64) -> Self {
+        Rotation {
+            yaw: Coord::Absolute(yaw),
+            pitch: Coord::Absolute(pitch),
+        }
+    }
+
+    /// The yaw and pitch this resolves to for an executor facing `rotation`. Panics if an axis is
+    /// local.
+    pub fn resolve(&self, rotation: (f64, f64)) -> (f64, f64) {
+        (self.yaw.resolve(rotation.0), self.pitch.resolve(rotation.1))
+    }
+}
+
+impl Display for Rotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.yaw, self.pitch)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::util::{Point3D, Version};
+
+    use super::{Coord, Position, Rotation};
+
+    #[test]
+    fn test_position() {
+        assert_eq!(Position::from(Point3D { x: -3, y: 64, z: 10 }).to_string(), "-3 64 10");
+        assert_eq!(
+            Position::from(Point3D {
+                x: 0.5,
+                y: 64.0,
+                z: -0.5
+            })
+            .to_string(),
+            "0.5 64 -0.5"
+        );
+        assert_eq!(Position::relative(0.0, 1.0, -2.5).to_string(), "~ ~1 ~-2.5");
+        assert_eq!(Position::local(0.0, 0.0, 3.0).to_string(), "^ ^ ^3");
+        assert_eq!(Position::here().to_string(), "~ ~ ~");
+
+        let mixed = Position {
+            y: Coord::Relative(1.0),
+            ..Position::absolute(10.0, 0.0, 20.0)
+        };
+        assert_eq!(mixed.to_string(), "10 ~1 20");
+
+        let rotation = Rotation {
+            pitch: Coord::Relative(0.0),
+            ..Rotation::absolute(90.0, 0.0)
+        };
+        assert_eq!(rotation.to_string(), "90 ~");
+    }
+
+    #[test]
+    fn test_resolve() {
+        let origin = Point3D {
+            x: 10.5,
+            y: 64.0,
+            z: -3.5,
+        };
+
+        let close = |a: Point3D<f64>, b: Point3D<f64>| (a.x - b.x).abs() + (a.y - b.y).abs() + (a.z - b.z).abs() < 1e-9;
+
+        let mixed = Position::new(Coord::Absolute(0.0), Coord::Relative(1.5), Coord::Relative(-1.0));
+        assert!(close(
+            mixed.resolve(origin, (0.0, 0.0)),
+            Point3D {
+                x: 0.0,
+                y: 65.5,
+                z: -4.5
+            }
+        ));
+        assert_eq!(mixed.resolve_block(origin, (0.0, 0.0)), Point3D { x: 0, y: 65, z: -5 });
+
+        // a yaw of 0 faces south (+z), 90 faces west (-x), a pitch of -90 faces up
+        let ahead = Position::local(0.0, 0.0, 2.0);
+        assert!(close(
+            ahead.resolve(origin, (0.0, 0.0)),
+            Point3D {
+                x: 10.5,
+                y: 64.0,
+                z: -1.5
+            }
+        ));
+        assert!(close(
+            ahead.resolve(origin, (90.0, 0.0)),
+            Point3D {
+                x: 8.5,
+                y: 64.0,
+                z: -3.5
+            }
+        ));
+        assert!(close(
+            ahead.resolve(origin, (0.0, -90.0)),
+            Point3D {
+                x: 10.5,
+                y: 66.0,
+                z: -3.5
+            }
+        ));
+
+        // facing south, left is east (+x) and up is up
+        let left_up = Position::local(1.0, 1.0, 0.0);
+        assert!(close(
+            left_up.resolve(origin, (0.0, 0.0)),
+            Point3D {
+                x: 11.5,
+                y: 65.0,
+                z: -3.5
+            }
+        ));
+
+        let rotation = Rotation {
+            yaw: Coord::Relative(45.0),
+            pitch: Coord::Absolute(10.0),
+        };
+        assert_eq!(rotation.resolve((90.0, -30.0)), (135.0, 10.0));
+
+        assert_eq!(
+            Position::local(1.0, 0.0, 0.0).offset(1.0, 0.0, -1.0).to_string(),
+            "^2 ^ ^-1"
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_mixed_local() {
+        Position::new(Coord::Local(1.0), Coord::Relative(0.0), Coord::Local(0.0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_legacy_local() {
+        Position::local(0.0, 0.0, 1.0).display(Version::V1_12).to_string();
+    }
+}
@@ -0,0 +1,413 @@
+//! Splits `fill` and `clone` commands into pieces the game accepts.
+//!
+//! The size of a cuboid is only known when both corners use the same kind of coordinate on every
+//! axis, with whole numbers. Local corners are never split, the box they span is not aligned with
+//! their axes.
+
+use crate::util::consts::MAX_FILL_VOLUME;
+
+use super::{Command, Coord, FillMode, Position};
+
+/// One axis of a cuboid: the kind of coordinate and the range of blocks covered, both included.
+#[derive(Copy, Clone)]
+struct Span {
+    kind: fn(f64) -> Coord,
+    lo: i64,
+    hi: i64,
+}
+
+impl Span {
+    fn new(a: Coord, b: Coord) -> Option<Self> {
+        let (kind, a, b): (fn(f64) -> Coord, f64, f64) = match (a, b) {
+            (Coord::Absolute(a), Coord::Absolute(b)) => (Coord::Absolute, a, b),
+            (Coord::Relative(a), Coord::Relative(b)) => (Coord::Relative, a, b),
+            _ => return None,
+        };
+
+        if a.fract() != 0.0 || b.fract() != 0.0 {
+            return None;
+        }
+
+        Some(Span {
+            kind,
+            lo: a.min(b) as i64,
+            hi: a.max(b) as i64,
+        })
+    }
+
+    fn len(&self) -> i64 {
+        self.hi - self.lo + 1
+    }
+
+    fn with(&self, lo: i64, hi: i64) -> Self {
+        Span { lo, hi, ..*self }
+    }
+
+    /// Cuts the span into `n` parts whose lengths differ by at most one.
+    fn parts(&self, n: i64) -> Vec<Span> {
+        let len = self.len();
+
+        (0..n)
+            .map(|i| self.with(self.lo + i * len / n, self.lo + (i + 1) * len / n - 1))
+            .collect()
+    }
+}
+
+type Cuboid = [Span; 3];
+
+fn cuboid(from: &Position, to: &Position) -> Option<Cuboid> {
+    Some([
+        Span::new(from.x, to.x)?,
+        Span::new(from.y, to.y)?,
+        Span::new(from.z, to.z)?,
+    ])
+}
+
+fn volume(c: &Cuboid) -> i64 {
+    c.iter().map(Span::len).product()
+}
+
+fn corners(c: &Cuboid) -> (Position, Position) {
+    let corner = |f: fn(&Span) -> i64| Position {
+        x: (c[0].kind)(f(&c[0]) as f64),
+        y: (c[1].kind)(f(&c[1]) as f64),
+        z: (c[2].kind)(f(&c[2]) as f64),
+    };
+
+    (corner(|s| s.lo), corner(|s| s.hi))
+}
+
+/// The distinct lengths a span of `len` blocks can be cut into equal parts of, the longest first.
+fn part_lengths(len: i64) -> Vec<i64> {
+    let mut lengths = Vec::new();
+    let mut n = 1;
+
+    while n <= len {
+        let l = (len + n - 1) / n;
+        lengths.push(l);
+
+        if l == 1 {
+            break;
+        }
+
+        // the fewest parts which are all shorter than `l`
+        n = (len + l - 2) / (l - 1);
+    }
+
+    lengths
+}
+
+/// Cuts `c` into the grid with the fewest cells of at most `limit` blocks each.
+fn grid(c: &Cuboid, limit: i64) -> Vec<Cuboid> {
+    if volume(c) <= limit {
+        return vec![*c];
+    }
+
+    let [w, h, d] = [c[0].len(), c[1].len(), c[2].len()];
+    let count = |len: i64, part: i64| (len + part - 1) / part;
+
+    let mut best = [w, h, d];
+    for &x in &part_lengths(w) {
+        for &y in &part_lengths(h) {
+            if x * y > limit {
+                continue;
+            }
+
+            let z = (limit / (x * y)).min(d);
+            let n = [count(w, x), count(h, y), count(d, z)];
+
+            if n.iter().product::<i64>() < best.iter().product::<i64>() {
+                best = n;
+            }
+        }
+    }
+
+    let mut cells = Vec::new();
+    for x in c[0].parts(best[0]) {
+        for y in c[1].parts(best[1]) {
+            for z in c[2].parts(best[2]) {
+                cells.push([x, y, z]);
+            }
+        }
+    }
+
+    cells
+}
+
+/// The faces of `c`, which cover its outer layer without overlapping. `None` if `c` has no inside.
+fn shell(c: &Cuboid) -> Option<(Vec<Cuboid>, Cuboid)> {
+    if c.iter().any(|s| s.len() <= 2) {
+        return None;
+    }
+
+    let [x, y, z] = *c;
+    let inner = [
+        x.with(x.lo + 1, x.hi - 1),
+        y.with(y.lo + 1, y.hi - 1),
+        z.with(z.lo + 1, z.hi - 1),
+    ];
+
+    let faces = vec![
+        [x, y.with(y.lo, y.lo), z],
+        [x, y.with(y.hi, y.hi), z],
+        [x.with(x.lo, x.lo), inner[1], z],
+        [x.with(x.hi, x.hi), inner[1], z],
+        [inner[0], inner[1], z.with(z.lo, z.lo)],
+        [inner[0], inner[1], z.with(z.hi, z.hi)],
+    ];
+
+    Some((faces, inner))
+}
+
+/// Splits `fill` and `clone` commands changing more than `MAX_FILL_VOLUME` blocks into several
+/// ones. Other commands, and those whose size can't be known, are returned as is.
+///
+/// `hollow` and `outline` fills are split into the faces of the cuboid, along with the air inside
+/// for `hollow`. Overlapping clones may differ once split, as each piece is copied on its own.
+pub fn split(cmd: Command) -> Vec<Command> {
+    split_with_limit(cmd, MAX_FILL_VOLUME)
+}
+
+fn split_with_limit(cmd: Command, limit: i64) -> Vec<Command> {
+    match cmd {
+        Command::Fill { from, to, block, mode } => {
+            let c = match cuboid(&from, &to) {
+                Some(c) if volume(&c) > limit => c,
+                _ => return vec![Command::Fill { from, to, block, mode }],
+            };
+
+            let fill = |c: &Cuboid, block, mode| {
+                let (from, to) = corners(c);
+                Command::Fill { from, to, block, mode }
+            };
+
+            let shell = match mode {
+                FillMode::Hollow | FillMode::Outline => shell(&c),
+                _ => None,
+            };
+
+            let (pieces, mode) = match shell {
+                Some((faces, inner)) => {
+                    let mut pieces: Vec<(Cuboid, _)> = faces.iter().map(|f| (*f, block.clone())).collect();
+
+                    if mode == FillMode::Hollow {
+                        pieces.push((inner, "air".into()));
+                    }
+
+                    (pieces, FillMode::Replace)
+                }
+                // without an inside, the shell is the whole cuboid
+                None if matches!(mode, FillMode::Hollow | FillMode::Outline) => (vec![(c, block)], FillMode::Replace),
+                None => (vec![(c, block)], mode),
+            };
+
+            pieces
+                .into_iter()
+                .flat_map(|(c, block)| {
+                    let mode = mode.clone();
+                    grid(&c, limit)
+                        .into_iter()
+                        .map(move |cell| fill(&cell, block.clone(), mode.clone()))
+                })
+                .collect()
+        }
+        Command::Clone {
+            from,
+            to,
+            destination,
+            mask,
+            mode,
+        } => {
+            let c = match cuboid(&from, &to) {
+                Some(c) if volume(&c) > limit && !destination.is_local() => c,
+                _ => {
+                    return vec![Command::Clone {
+                        from,
+                        to,
+                        destination,
+                        mask,
+                        mode,
+                    }]
+                }
+            };
+
+            grid(&c, limit)
+                .into_iter()
+                .map(|cell| {
+                    let (from, to) = corners(&cell);
+
+                    Command::Clone {
+                        from,
+                        to,
+                        destination: destination.offset(
+                            (cell[0].lo - c[0].lo) as f64,
+                            (cell[1].lo - c[1].lo) as f64,
+                            (cell[2].lo - c[2].lo) as f64,
+                        ),
+                        mask: mask.clone(),
+                        mode,
+                    }
+                })
+                .collect()
+        }
+        cmd => vec![cmd],
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::cmd::{BlockState, CloneMask, CloneMode, Command, Coord, FillMode, Position};
+    use crate::util::consts::MAX_FILL_VOLUME;
+
+    use super::{cuboid, part_lengths, split, split_with_limit, volume, Cuboid};
+
+    fn fill(from: Position, to: Position, mode: FillMode) -> Command {
+        Command::Fill {
+            from,
+            to,
+            block: BlockState::new("stone"),
+            mode,
+        }
+    }
+
+    fn cuboids(cmds: &[Command]) -> Vec<Cuboid> {
+        cmds.iter()
+            .map(|cmd| match cmd {
+                Command::Fill { from, to, .. } | Command::Clone { from, to, .. } => cuboid(from, to).unwrap(),
+                _ => panic!("not a fill or clone"),
+            })
+            .collect()
+    }
+
+    /// The blocks covered by `cuboids`, panics if two overlap.
+    fn covered(cuboids: &[Cuboid]) -> i64 {
+        for (i, a) in cuboids.iter().enumerate() {
+            for b in &cuboids[i + 1..] {
+                let disjoint = a.iter().zip(b).any(|(a, b)| a.hi < b.lo || b.hi < a.lo);
+                assert!(disjoint, "overlapping pieces");
+            }
+        }
+
+        cuboids.iter().map(volume).sum()
+    }
+
+    #[test]
+    fn test_part_lengths() {
+        assert_eq!(part_lengths(1), [1]);
+        assert_eq!(part_lengths(10), [10, 5, 4, 3, 2, 1]);
+        assert!(part_lengths(60_000_000).len() < 20_000);
+    }
+
+    #[test]
+    fn test_split_fill() {
+        // small enough
+        let small = fill(
+            Position::absolute(0.0, 0.0, 0.0),
+            Position::absolute(31.0, 31.0, 31.0),
+            FillMode::Keep,
+        );
+        assert_eq!(split(small.clone()), [small]);
+
+        // a 100x384x100 column needs at least 118 pieces
+        let big = fill(
+            Position::absolute(100.0, 319.0, 100.0),
+            Position::absolute(1.0, -64.0, 1.0),
+            FillMode::Destroy,
+        );
+        let cmds = split(big);
+        let pieces = cuboids(&cmds);
+
+        assert!(pieces.len() <= 125);
+        assert!(pieces.iter().all(|c| volume(c) <= MAX_FILL_VOLUME));
+        assert_eq!(covered(&pieces), 100 * 384 * 100);
+        assert!(cmds.iter().all(|c| c.to_string().ends_with(" stone destroy")));
+
+        // relative corners split the same way
+        let relative = fill(
+            Position::relative(0.0, 0.0, 0.0),
+            Position::relative(99.0, 383.0, 99.0),
+            FillMode::Replace,
+        );
+        let cmds = split(relative);
+        assert_eq!(covered(&cuboids(&cmds)), 100 * 384 * 100);
+        assert!(cmds[0].to_string().starts_with("fill ~ ~ ~ "));
+
+        // the size of mixed or local corners is unknown
+        let mixed = Position {
+            x: Coord::Absolute(1000.0),
+            ..Position::relative(0.0, 0.0, 0.0)
+        };
+        let unknown = fill(Position::relative(0.0, 0.0, 0.0), mixed, FillMode::Replace);
+        assert_eq!(split(unknown.clone()), [unknown]);
+
+        let local = fill(
+            Position::local(0.0, 0.0, 0.0),
+            Position::local(99.0, 99.0, 99.0),
+            FillMode::Replace,
+        );
+        assert_eq!(split(local.clone()), [local]);
+    }
+
+    #[test]
+    fn test_split_hollow() {
+        let hollow = fill(
+            Position::absolute(0.0, 0.0, 0.0),
+            Position::absolute(9.0, 9.0, 9.0),
+            FillMode::Hollow,
+        );
+        let cmds = split_with_limit(hollow, 100);
+        let pieces = cuboids(&cmds);
+
+        assert_eq!(covered(&pieces), 1000);
+        assert!(pieces.iter().all(|c| volume(c) <= 100));
+
+        // the inside is air, the outer layer stone
+        for (cmd, c) in cmds.iter().zip(&pieces) {
+            let inside = c.iter().all(|s| s.lo >= 1 && s.hi <= 8);
+            assert_eq!(cmd.to_string().ends_with(" air"), inside);
+        }
+
+        let outline = fill(
+            Position::absolute(0.0, 0.0, 0.0),
+            Position::absolute(9.0, 9.0, 9.0),
+            FillMode::Outline,
+        );
+        assert_eq!(covered(&cuboids(&split_with_limit(outline, 100))), 1000 - 8 * 8 * 8);
+
+        // too thin to have an inside
+        let thin = fill(
+            Position::absolute(0.0, 0.0, 0.0),
+            Position::absolute(99.0, 1.0, 99.0),
+            FillMode::Hollow,
+        );
+        assert_eq!(covered(&cuboids(&split_with_limit(thin, 1000))), 20000);
+    }
+
+    #[test]
+    fn test_split_clone() {
+        let clone = Command::Clone {
+            from: Position::absolute(0.0, 0.0, 0.0),
+            to: Position::absolute(63.0, 63.0, 63.0),
+            destination: Position::relative(0.0, 10.0, 0.0),
+            mask: CloneMask::Masked,
+            mode: CloneMode::Force,
+        };
+
+        let cmds = split(clone);
+        assert_eq!(cmds.len(), 8);
+        assert_eq!(covered(&cuboids(&cmds)), 64 * 64 * 64);
+
+        // every piece lands where it would have been copied to
+        for cmd in &cmds {
+            match cmd {
+                Command::Clone { from, destination, .. } => {
+                    assert_eq!(destination.x, Coord::Relative(from.x.resolve(0.0)));
+                    assert_eq!(destination.y, Coord::Relative(10.0 + from.y.resolve(0.0)));
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        assert!(cmds[7].to_string().ends_with(" masked force"));
+    }
+}
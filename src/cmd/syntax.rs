@@ -0,0 +1,26 @@
+//! Writing commands for a given game version.
+//!
+//! Commands are modelled after the latest syntax and written for older versions where they
+//! differ: data values and `/blockdata` before 1.13, double quoted strings only before 1.14 and
+//! item NBT instead of item components before 1.20.5.
+
+use std::fmt::{self, Display};
+
+use crate::util::Version;
+
+/// The version `Display` implementations write for.
+pub(super) const LATEST: Version = Version::V1_21;
+
+/// A value written differently depending on the game version.
+pub(super) trait Syntax {
+    fn write(&self, f: &mut fmt::Formatter<'_>, version: Version) -> fmt::Result;
+}
+
+/// `value` written for `version`.
+pub(super) struct Versioned<'a, T: ?Sized>(pub &'a T, pub Version);
+
+impl<T: Syntax + ?Sized> Display for Versioned<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.write(f, self.1)
+    }
+}
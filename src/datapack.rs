@@ -0,0 +1,360 @@
+//! Datapacks of functions built from `cmd` commands, written as a directory or a zip file.
+//!
+//! Any command list can become a function, the output of `cmd::fill`, `cmd::walls_3d` or
+//! `cmd::chain` for example, as long as it is valid in the version the pack is written for.
+
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Seek, Write};
+use std::path::Path;
+
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::cmd::Command;
+use crate::util::Version;
+
+/// A datapack with functions in a single namespace.
+pub struct Datapack {
+    namespace: String,
+    description: String,
+    version: Version,
+    functions: Vec<(String, Vec<Command>)>,
+    load: Vec<String>,
+    tick: Vec<String>,
+}
+
+impl Datapack {
+    /// An empty pack for `version`, with functions in `namespace`. Panics if the version has no
+    /// datapacks, before 1.13, or the namespace is not a valid one.
+    pub fn new(namespace: &str, version: Version) -> Self {
+        assert!(version >= Version::V1_13, "datapacks were added in 1.13");
+        assert!(
+            is_valid(namespace, false),
+            "namespaces only have lowercase letters, digits and _-.: {}",
+            namespace
+        );
+
+        Datapack {
+            namespace: namespace.to_string(),
+            description: String::new(),
+            version,
+            functions: Vec::new(),
+            load: Vec::new(),
+            tick: Vec::new(),
+        }
+    }
+
+    /// Sets the description shown in the pack list.
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.description = description.to_string();
+        self
+    }
+
+    /// Adds the function `name`, running `cmds` in order, replacing any previous one. Names may
+    /// have directories, `farm/clear` for example. Panics if the name is not a valid one.
+    pub fn with_function<I: IntoIterator<Item = Command>>(mut self, name: &str, cmds: I) -> Self {
+        assert!(
+            is_valid(name, true),
+            "function names only have lowercase letters, digits and _-./: {}",
+            name
+        );
+
+        let cmds = cmds.into_iter().collect();
+        match self.functions.iter_mut().find(|(n, _)| n == name) {
+            Some(function) => function.1 = cmds,
+            None => self.functions.push((name.to_string(), cmds)),
+        }
+
+        self
+    }
+
+    /// Runs the function `name` when the pack is loaded, see `files`.
+    pub fn with_load(mut self, name: &str) -> Self {
+        self.load.push(name.to_string());
+        self
+    }
+
+    /// Runs the function `name` every tick, see `files`.
+    pub fn with_tick(mut self, name: &str) -> Self {
+        self.tick.push(name.to_string());
+        self
+    }
+
+    /// The `pack_format` of the version the pack is written for.
+    pub fn pack_format(&self) -> u32 {
+        pack_format(self.version)
+    }
+
+    /// The files of the pack, as their path from its root and their content. Panics if a `load`
+    /// or `tick` function was not added, or a command can't be written for the pack's version,
+    /// see `Command::display`.
+    pub fn files(&self) -> Vec<(String, String)> {
+        // the folders lost their plural in 1.21
+        let functions = if self.version >= Version::V1_21 {
+            "function"
+        } else {
+            "functions"
+        };
+
+        let mut files = vec![(
+            "pack.mcmeta".to_string(),
+            format!(
+                "{{\"pack\":{{\"pack_format\":{},\"description\":{}}}}}\n",
+                self.pack_format(),
+                json_string(&self.description)
+            ),
+        )];
+
+        for (name, cmds) in &self.functions {
+            let lines: String = cmds
+                .iter()
+                .map(|cmd| format!("{}\n", cmd.display(self.version)))
+                .collect();
+
+            files.push((
+                format!("data/{}/{}/{}.mcfunction", self.namespace, functions, name),
+                lines,
+            ));
+        }
+
+        for (tag, names) in &[("load", &self.load), ("tick", &self.tick)] {
+            if names.is_empty() {
+                continue;
+            }
+
+            let values: Vec<String> = names
+                .iter()
+                .map(|name| {
+                    assert!(
+                        self.functions.iter().any(|(n, _)| n == name),
+                        "no function {} to {}",
+                        name,
+                        tag
+                    );
+
+                    json_string(&format!("{}:{}", self.namespace, name))
+                })
+                .collect();
+
+            files.push((
+                format!("data/minecraft/tags/{}/{}.json", functions, tag),
+                format!("{{\"values\":[{}]}}\n", values.join(",")),
+            ));
+        }
+
+        files
+    }
+
+    /// Writes the pack as the directory `path`, which is created if needed. Panics like `files`.
+    pub fn write_dir<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        for (name, content) in self.files() {
+            let file = path.as_ref().join(name);
+
+            if let Some(parent) = file.parent() {
+                fs::create_dir_all(parent)?;
+            }
+
+            fs::write(file, content)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the pack as a zip file. Panics like `files`.
+    pub fn write_zip<W: Write + Seek>(&self, w: W) -> io::Result<()> {
+        let mut zip = ZipWriter::new(w);
+        let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+        for (name, content) in self.files() {
+            zip.start_file(name, options)?;
+            zip.write_all(content.as_bytes())?;
+        }
+
+        zip.finish()?;
+        Ok(())
+    }
+
+    pub fn save_zip<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.write_zip(BufWriter::new(File::create(path)?))
+    }
+}
+
+/// The data pack format of `version`. Versions after 1.21.5 use its format. Panics before 1.13.
+pub fn pack_format(version: Version) -> u32 {
+    let formats = [
+        (Version::new(21, 5), 71),
+        (Version::new(21, 4), 61),
+        (Version::new(21, 2), 57),
+        (Version::V1_21, 48),
+        (Version::V1_20_5, 41),
+        (Version::new(20, 3), 26),
+        (Version::new(20, 2), 18),
+        (Version::V1_20, 15),
+        (Version::new(19, 4), 12),
+        (Version::V1_19, 10),
+        (Version::V1_18_2, 9),
+        (Version::V1_18, 8),
+        (Version::new(17, 0), 7),
+        (Version::new(16, 2), 6),
+        (Version::new(15, 0), 5),
+        (Version::V1_13, 4),
+    ];
+
+    match formats.iter().find(|(v, _)| version >= *v) {
+        Some((_, format)) => *format,
+        None => panic!("datapacks were added in 1.13"),
+    }
+}
+
+/// Whether `s` is a valid namespace, or a valid path if `path` is set.
+fn is_valid(s: &str, path: bool) -> bool {
+    !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || "_-.".contains(c) || (path && c == '/'))
+}
+
+/// `s` as a JSON string.
+fn json_string(s: &str) -> String {
+    let mut res = String::with_capacity(s.len() + 2);
+    res.push('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => res.push_str("\\\""),
+            '\\' => res.push_str("\\\\"),
+            '\n' => res.push_str("\\n"),
+            c if (c as u32) < 0x20 => res.push_str(&format!("\\u{:04x}", c as u32)),
+            c => res.push(c),
+        }
+    }
+
+    res.push('"');
+    res
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::{Cursor, Read};
+
+    use zip::ZipArchive;
+
+    use crate::cmd::{self, Command, Position};
+    use crate::util::{Dimension, Point3D, Version};
+
+    use super::{pack_format, Datapack};
+
+    fn pack(version: Version) -> Datapack {
+        let center = Point3D { x: 0, y: 64, z: 0 };
+
+        Datapack::new("farm", version)
+            .with_description("A \"perimeter\"")
+            .with_function("walls", cmd::walls_3d(center, 200, "glass", Dimension::Overworld, version))
+            .with_function(
+                "setup/clear",
+                cmd::fill(
+                    Position::relative(-8.0, 0.0, -8.0),
+                    Position::relative(8.0, 4.0, 8.0),
+                    "air",
+                ),
+            )
+            .with_function(
+                "timer",
+                vec![Command::Raw("scoreboard players add #t timer 1".to_string())],
+            )
+            .with_function(
+                "setup/blocks",
+                cmd::chain_blocks(vec![cmd::setblock(center, "stone")], center, "up", version),
+            )
+            .with_load("setup/clear")
+            .with_tick("timer")
+    }
+
+    #[test]
+    fn test_files() {
+        assert_eq!(pack_format(Version::V1_13), 4);
+        assert_eq!(pack_format(Version::V1_20_5), 41);
+        assert_eq!(pack_format(Version::new(20, 6)), 41);
+        assert_eq!(pack_format(Version::V1_21), 48);
+
+        let files = pack(Version::V1_21).files();
+        let names: Vec<&str> = files.iter().map(|(name, _)| name.as_str()).collect();
+
+        assert_eq!(
+            names,
+            [
+                "pack.mcmeta",
+                "data/farm/function/walls.mcfunction",
+                "data/farm/function/setup/clear.mcfunction",
+                "data/farm/function/timer.mcfunction",
+                "data/farm/function/setup/blocks.mcfunction",
+                "data/minecraft/tags/function/load.json",
+                "data/minecraft/tags/function/tick.json",
+            ]
+        );
+
+        assert_eq!(
+            files[0].1,
+            "{\"pack\":{\"pack_format\":48,\"description\":\"A \\\"perimeter\\\"\"}}\n"
+        );
+        assert_eq!(files[5].1, "{\"values\":[\"farm:setup/clear\"]}\n");
+
+        assert_eq!(
+            files[4].1,
+            "setblock 0 64 0 command_block[facing=up]{Command:\"setblock 0 64 0 stone\",auto:1b}\n"
+        );
+
+        // the walls are split to fit the fill limit
+        assert_eq!(files[1].1.lines().count(), 12);
+        assert!(files[1].1.starts_with("fill -100 -64 -100 -100 319 -34 glass\n"));
+
+        let files = pack(Version::V1_16).files();
+        assert_eq!(files[3].0, "data/farm/functions/timer.mcfunction");
+        assert_eq!(files[6].0, "data/minecraft/tags/functions/tick.json");
+
+        // the overworld only spans 0..256 before 1.18
+        assert_eq!(
+            files[1].1,
+            "fill -100 0 -100 -100 255 -1 glass\n\
+             fill -100 0 0 -100 255 100 glass\n\
+             fill -100 0 100 100 127 100 glass\n\
+             fill -100 128 100 100 255 100 glass\n\
+             fill 100 0 -100 100 255 -1 glass\n\
+             fill 100 0 0 100 255 100 glass\n\
+             fill -100 0 -100 100 127 -100 glass\n\
+             fill -100 128 -100 100 255 -100 glass\n"
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_missing_tick() {
+        Datapack::new("farm", Version::V1_21).with_tick("missing").files();
+    }
+
+    #[test]
+    fn test_write() {
+        let pack = pack(Version::V1_21);
+
+        let mut zip = Cursor::new(Vec::new());
+        pack.write_zip(&mut zip).unwrap();
+
+        let mut archive = ZipArchive::new(zip).unwrap();
+        assert_eq!(archive.len(), 7);
+
+        let mut timer = String::new();
+        archive
+            .by_name("data/farm/function/timer.mcfunction")
+            .unwrap()
+            .read_to_string(&mut timer)
+            .unwrap();
+        assert_eq!(timer, "scoreboard players add #t timer 1\n");
+
+        let dir = std::env::temp_dir().join(format!("mc_utils_datapack_{}", std::process::id()));
+        pack.write_dir(&dir).unwrap();
+
+        let tick = std::fs::read_to_string(dir.join("data/minecraft/tags/function/tick.json")).unwrap();
+        assert_eq!(tick, "{\"values\":[\"farm:timer\"]}\n");
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
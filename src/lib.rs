@@ -28,4 +28,19 @@ pub mod rand;
 */
 pub mod convert;
 
+/**
+* For structure placement prediction
+*/
+pub mod structure;
+
+/**
+* For biome prediction
+*/
+pub mod biome;
+
 pub mod alg;
+
+/**
+* For datapack generation from commands
+*/
+pub mod datapack;
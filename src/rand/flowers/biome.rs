@@ -0,0 +1,208 @@
+use once_cell::sync::Lazy;
+
+use crate::rand::noise::NormalNoise;
+use crate::util::{BlockPoint, Point3D};
+
+use super::{flower_at, flower_noise, pick_index, sample_at, FlowerForestFlower, FLOWER_NOISE};
+
+// plains use a `NoiseThresholdProvider` over the same noise as flower forests
+const PLAINS_SCALE: f64 = 0.005f32 as f64;
+const PLAINS_THRESHOLD: f64 = -0.8;
+const PLAINS_HIGH_CHANCE: f64 = 0.33333334f32 as f64;
+
+const PLAINS_LOW_FLOWERS: [Flower; 4] = [
+    Flower::OrangeTulip,
+    Flower::RedTulip,
+    Flower::PinkTulip,
+    Flower::WhiteTulip,
+];
+
+const PLAINS_HIGH_FLOWERS: [Flower; 4] = [
+    Flower::Poppy,
+    Flower::AzureBluet,
+    Flower::OxeyeDaisy,
+    Flower::Cornflower,
+];
+
+// meadows use a `DualNoiseProvider`: a slow noise picks a small subset of the list, then the main
+// noise picks a state out of that subset
+const MEADOW_SCALE: f64 = 1.0;
+const MEADOW_SLOW_SCALE: f32 = 1.0;
+const MEADOW_VARIETY: (i32, i32) = (1, 3);
+
+static MEADOW_NOISE: Lazy<NormalNoise> = Lazy::new(|| flower_noise(-3));
+static MEADOW_SLOW_NOISE: Lazy<NormalNoise> = Lazy::new(|| flower_noise(-10));
+
+const MEADOW_FLOWERS: [Flower; 8] = [
+    Flower::TallGrass,
+    Flower::Allium,
+    Flower::Poppy,
+    Flower::AzureBluet,
+    Flower::Dandelion,
+    Flower::Cornflower,
+    Flower::OxeyeDaisy,
+    Flower::ShortGrass,
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+/// Any block that can be placed by the flower feature of a biome.
+///
+/// NOTE: meadows also place grass through their flower feature, hence the grass variants.
+pub enum Flower {
+    Dandelion,
+    Poppy,
+    BlueOrchid,
+    Allium,
+    AzureBluet,
+    RedTulip,
+    OrangeTulip,
+    WhiteTulip,
+    PinkTulip,
+    OxeyeDaisy,
+    Cornflower,
+    LilyOfTheValley,
+    PinkPetals,
+    ShortGrass,
+    TallGrass,
+}
+
+impl From<FlowerForestFlower> for Flower {
+    fn from(value: FlowerForestFlower) -> Self {
+        match value {
+            FlowerForestFlower::Dandelion => Flower::Dandelion,
+            FlowerForestFlower::Poppy => Flower::Poppy,
+            FlowerForestFlower::Allium => Flower::Allium,
+            FlowerForestFlower::AzureBluet => Flower::AzureBluet,
+            FlowerForestFlower::RedTulip => Flower::RedTulip,
+            FlowerForestFlower::OrangeTulip => Flower::OrangeTulip,
+            FlowerForestFlower::WhiteTulip => Flower::WhiteTulip,
+            FlowerForestFlower::PinkTulip => Flower::PinkTulip,
+            FlowerForestFlower::OxeyeDaisy => Flower::OxeyeDaisy,
+            FlowerForestFlower::Cornflower => Flower::Cornflower,
+            FlowerForestFlower::LilyOfTheValley => Flower::LilyOfTheValley,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+/// Any biome with its own flower feature.
+pub enum FlowerBiome {
+    Plains,
+    SunflowerPlains,
+    Meadow,
+    Swamp,
+    CherryGrove,
+    FlowerForest,
+}
+
+/// Computes the flowers which can be placed at the given coordinate in the given biome, along with
+/// the probability of each one being picked.
+///
+/// Most biomes pick their flower from noise alone and return a single flower with probability
+/// `1.0`. Plains also roll the feature's random source, which cannot be predicted from the block
+/// position alone.
+pub fn flowers_at(p: BlockPoint, biome: FlowerBiome) -> Vec<(Flower, f64)> {
+    match biome {
+        FlowerBiome::Plains | FlowerBiome::SunflowerPlains => plains_flowers_at(p),
+        FlowerBiome::Meadow => vec![(meadow_flower_at(p), 1f64)],
+        FlowerBiome::Swamp => vec![(Flower::BlueOrchid, 1f64)],
+        FlowerBiome::CherryGrove => vec![(Flower::PinkPetals, 1f64)],
+        FlowerBiome::FlowerForest => vec![(flower_at(p).into(), 1f64)],
+    }
+}
+
+fn plains_flowers_at(p: BlockPoint) -> Vec<(Flower, f64)> {
+    let val = sample_at(&FLOWER_NOISE, p, PLAINS_SCALE);
+
+    if val < PLAINS_THRESHOLD {
+        let chance = 1f64 / PLAINS_LOW_FLOWERS.len() as f64;
+
+        return PLAINS_LOW_FLOWERS.iter().map(|f| (*f, chance)).collect();
+    }
+
+    let chance = PLAINS_HIGH_CHANCE / PLAINS_HIGH_FLOWERS.len() as f64;
+
+    PLAINS_HIGH_FLOWERS
+        .iter()
+        .map(|f| (*f, chance))
+        .chain(std::iter::once((Flower::Dandelion, 1f64 - PLAINS_HIGH_CHANCE)))
+        .collect()
+}
+
+fn meadow_flower_at(p: BlockPoint) -> Flower {
+    let (min, max) = MEADOW_VARIETY;
+
+    // `Mth.clampedMap(d, -1.0, 1.0, min, max + 1)`
+    let delta = ((slow_sample_at(p) + 1f64) / 2f64).clamp(0f64, 1f64);
+    let variety = (min as f64 + delta * (max + 1 - min) as f64) as i32;
+
+    let flowers: Vec<Flower> = (0..variety)
+        .map(|j| {
+            let offset = Point3D {
+                x: p.x + j * 54545,
+                y: p.y,
+                z: p.z + j * 34234,
+            };
+
+            MEADOW_FLOWERS[pick_index(slow_sample_at(offset), MEADOW_FLOWERS.len())]
+        })
+        .collect();
+
+    let val = sample_at(&MEADOW_NOISE, p, MEADOW_SCALE);
+    flowers[pick_index(val, flowers.len())]
+}
+
+/// The slow noise coordinates are scaled as `float`s in the vanilla source.
+fn slow_sample_at(p: BlockPoint) -> f64 {
+    MEADOW_SLOW_NOISE.sample(Point3D {
+        x: (p.x as f32 * MEADOW_SLOW_SCALE) as f64,
+        y: (p.y as f32 * MEADOW_SLOW_SCALE) as f64,
+        z: (p.z as f32 * MEADOW_SLOW_SCALE) as f64,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::rand::{flower_at, flowers_at, Flower, FlowerBiome};
+    use crate::util::Point3D;
+
+    #[test]
+    fn test_flower_forest_matches_flower_at() {
+        for x in -20..20 {
+            let p = Point3D { x: x * 7, y: -60, z: x * 13 };
+
+            assert_eq!(flowers_at(p, FlowerBiome::FlowerForest), vec![(flower_at(p).into(), 1f64)]);
+        }
+    }
+
+    #[test]
+    fn test_single_flower_biomes() {
+        let p = Point3D { x: 12, y: 64, z: -40 };
+
+        assert_eq!(flowers_at(p, FlowerBiome::Swamp), vec![(Flower::BlueOrchid, 1f64)]);
+        assert_eq!(flowers_at(p, FlowerBiome::CherryGrove), vec![(Flower::PinkPetals, 1f64)]);
+    }
+
+    #[test]
+    fn test_plains_probabilities() {
+        for x in -20..20 {
+            let p = Point3D { x: x * 31, y: 64, z: x * 17 };
+            let flowers = flowers_at(p, FlowerBiome::Plains);
+            let total: f64 = flowers.iter().map(|(_, chance)| chance).sum();
+
+            assert!((total - 1f64).abs() < 1e-6);
+            assert_eq!(flowers, flowers_at(p, FlowerBiome::SunflowerPlains));
+        }
+    }
+
+    #[test]
+    fn test_meadow_is_deterministic() {
+        for x in -20..20 {
+            let p = Point3D { x: x * 5, y: 100, z: x * 3 };
+            let flowers = flowers_at(p, FlowerBiome::Meadow);
+
+            assert_eq!(flowers.len(), 1);
+            assert_eq!(flowers, flowers_at(p, FlowerBiome::Meadow));
+        }
+    }
+}
@@ -1,37 +1,27 @@
-mod noise;
+mod biome;
 
+use crate::rand::noise::{NoiseParameters, NormalNoise};
+use crate::rand::source::LegacyRandomSource;
 use crate::util::{BlockPoint, Point3D};
 use once_cell::sync::Lazy;
 
-/// NOTE: For Minecraft versions prior to 1.18, the simplex noise algorithm was used to compute
-/// flower positions. The current implementation only implements perlin noise, which is correct for
-/// any game version after and including 1.18.
+pub use biome::{flowers_at, Flower, FlowerBiome};
 
-// this is the flower seed present in the vanilla source code
-const FLOWER_SEED: u64 = 2345;
-const SCALE_1: f64 = 0.02083333395421505;
-const SCALE_2: f64 = SCALE_1 * 1.0181268882175227;
-const PERLIN_AMPLITUDE: f64 = 0.8333333333333333;
-
-const OCTAVE_HASHCODE: i64 = 1261148513;
-
-// these seeds are the first 2 nextLong calls of new Random(global_flower_seed) (for 2345: -1223197305642693068, -8087649459364435462)
-// then xor with "octave_0".hashCode() (1261148513)
-static PERLIN_SEEDS: Lazy<(i64, i64)> = Lazy::new(|| {
-    let mut rnd = java_rand::Random::new(FLOWER_SEED);
+// NOTE: For Minecraft versions prior to 1.18, the simplex noise algorithm was used to compute
+// flower positions. The current implementation only implements perlin noise, which is correct for
+// any game version after and including 1.18.
 
-    let seed1 = rnd.next_i64();
-    let seed2 = rnd.next_i64();
-
-    assert_eq!(seed1, -1223197305642693068);
-    assert_eq!(seed2, -8087649459364435462);
+// this is the flower seed present in the vanilla source code
+const FLOWER_SEED: i64 = 2345;
+const FLOWER_FOREST_SCALE: f64 = 0.020833334f32 as f64;
 
-    (seed1 ^ OCTAVE_HASHCODE, seed2 ^ OCTAVE_HASHCODE)
-});
+// the seeds of the samplers are the first 2 nextLong calls of new Random(FLOWER_SEED) (for 2345:
+// -1223197305642693068, -8087649459364435462) xored with "octave_0".hashCode() (1261148513)
+static FLOWER_NOISE: Lazy<NormalNoise> = Lazy::new(|| flower_noise(0));
 
 const NUM_FLOWERS_TYPES: i32 = 11;
 
-#[derive(PartialEq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 /// Any flower that can naturally generate in a flower forest biome.
 pub enum FlowerForestFlower {
     Dandelion,
@@ -47,47 +37,53 @@ pub enum FlowerForestFlower {
     LilyOfTheValley,
 }
 
+impl FlowerForestFlower {
+    /// Every flower forest flower, in the order they are picked by the flower noise.
+    pub const ALL: [FlowerForestFlower; NUM_FLOWERS_TYPES as usize] = [
+        FlowerForestFlower::Dandelion,
+        FlowerForestFlower::Poppy,
+        FlowerForestFlower::Allium,
+        FlowerForestFlower::AzureBluet,
+        FlowerForestFlower::RedTulip,
+        FlowerForestFlower::OrangeTulip,
+        FlowerForestFlower::WhiteTulip,
+        FlowerForestFlower::PinkTulip,
+        FlowerForestFlower::OxeyeDaisy,
+        FlowerForestFlower::Cornflower,
+        FlowerForestFlower::LilyOfTheValley,
+    ];
+}
+
 /// Computes the flower at the given coordinate
 pub fn flower_at(p: BlockPoint) -> FlowerForestFlower {
-    let noise1 = noise::perlin(
-        Point3D {
-            x: (p.x as f64) * SCALE_1,
-            y: (p.y as f64) * SCALE_1,
-            z: (p.z as f64) * SCALE_1,
-        },
-        Some(PERLIN_SEEDS.0),
-    );
-
-    let noise2 = noise::perlin(
-        Point3D {
-            x: (p.x as f64) * SCALE_2,
-            y: (p.y as f64) * SCALE_2,
-            z: (p.z as f64) * SCALE_2,
-        },
-        Some(PERLIN_SEEDS.1),
-    );
-
-    let val = (noise1 + noise2) * PERLIN_AMPLITUDE - PERLIN_AMPLITUDE + 0.5;
-
-    assert!(val <= 1f64);
-    assert!(0f64 <= val);
-
-    let flower_id = (val * NUM_FLOWERS_TYPES as f64).floor() as usize;
-
-    match flower_id {
-        0 => FlowerForestFlower::Dandelion,
-        1 => FlowerForestFlower::Poppy,
-        2 => FlowerForestFlower::Allium,
-        3 => FlowerForestFlower::AzureBluet,
-        4 => FlowerForestFlower::RedTulip,
-        5 => FlowerForestFlower::OrangeTulip,
-        6 => FlowerForestFlower::WhiteTulip,
-        7 => FlowerForestFlower::PinkTulip,
-        8 => FlowerForestFlower::OxeyeDaisy,
-        9 => FlowerForestFlower::Cornflower,
-        10 => FlowerForestFlower::LilyOfTheValley,
-        _ => unreachable!(),
-    }
+    let val = sample_at(&FLOWER_NOISE, p, FLOWER_FOREST_SCALE);
+
+    FlowerForestFlower::ALL[pick_index(val, NUM_FLOWERS_TYPES as usize)]
+}
+
+/// The single octave noise every flower provider uses, seeded with `FLOWER_SEED`.
+fn flower_noise(first_octave: i32) -> NormalNoise {
+    let params = NoiseParameters::new(first_octave, &[1f64]);
+
+    NormalNoise::new(&mut LegacyRandomSource::new(FLOWER_SEED), &params)
+}
+
+/// Samples `noise` at the block `p` scaled by `scale`, as done by `NoiseBasedStateProvider`.
+fn sample_at(noise: &NormalNoise, p: BlockPoint, scale: f64) -> f64 {
+    noise.sample(Point3D {
+        x: (p.x as f64) * scale,
+        y: (p.y as f64) * scale,
+        z: (p.z as f64) * scale,
+    })
+}
+
+/// Maps a noise value to an index in a list of `len` states, as done by `getRandomState` in the
+/// vanilla source.
+fn pick_index(noise: f64, len: usize) -> usize {
+    // the game clamps the noise value before picking a state
+    let val = ((1f64 + noise) / 2f64).clamp(0f64, 0.9999);
+
+    (val * len as f64) as usize
 }
 
 #[cfg(test)]
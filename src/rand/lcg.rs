@@ -0,0 +1,216 @@
+//! Helpers for manipulating the state of `java.util.Random` directly.
+//!
+//! Every function here works on the *internal* 48 bit seed of the LCG, which is the seed passed to
+//! `new Random(seed)` after scrambling. Use `scramble` to convert between the two.
+
+pub const MULTIPLIER: i64 = 0x5DEECE66D;
+pub const ADDEND: i64 = 0xB;
+pub const MASK: i64 = (1 << 48) - 1;
+
+/// An affine step `seed -> seed * multiplier + addend (mod 2^48)`. Any number of steps of the java
+/// LCG, forward or backward, is itself an `Lcg`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Lcg {
+    pub multiplier: i64,
+    pub addend: i64,
+}
+
+impl Lcg {
+    /// The LCG of `java.util.Random`.
+    pub const JAVA: Lcg = Lcg {
+        multiplier: MULTIPLIER,
+        addend: ADDEND,
+    };
+
+    /// The step which does nothing.
+    pub const IDENTITY: Lcg = Lcg {
+        multiplier: 1,
+        addend: 0,
+    };
+
+    pub fn next_seed(&self, seed: i64) -> i64 {
+        seed.wrapping_mul(self.multiplier).wrapping_add(self.addend) & MASK
+    }
+
+    /// Returns the step applying `self`, then `other`.
+    pub fn combine(&self, other: &Lcg) -> Lcg {
+        Lcg {
+            multiplier: self.multiplier.wrapping_mul(other.multiplier) & MASK,
+            addend: self.addend.wrapping_mul(other.multiplier).wrapping_add(other.addend) & MASK,
+        }
+    }
+
+    /// Returns the step undoing `self`. The multiplier of any java LCG step is odd, hence invertible.
+    pub fn invert(&self) -> Lcg {
+        assert!(self.multiplier & 1 == 1, "only odd multipliers can be inverted");
+
+        // newton's iteration doubles the number of correct low bits each time
+        let mut inv = self.multiplier;
+        for _ in 0..5 {
+            inv = inv.wrapping_mul(2i64.wrapping_sub(self.multiplier.wrapping_mul(inv)));
+        }
+
+        let multiplier = inv & MASK;
+
+        Lcg {
+            multiplier,
+            addend: multiplier.wrapping_mul(self.addend).wrapping_neg() & MASK,
+        }
+    }
+
+    /// Returns the step applying `self` `steps` times, in `O(log steps)`. Negative values step
+    /// backward.
+    pub fn combine_n(&self, steps: i64) -> Lcg {
+        let base = if steps < 0 { self.invert() } else { *self };
+        let mut n = steps.unsigned_abs();
+
+        let mut res = Lcg::IDENTITY;
+        let mut pow = base;
+
+        while n > 0 {
+            if n & 1 == 1 {
+                res = res.combine(&pow);
+            }
+
+            pow = pow.combine(&pow);
+            n >>= 1;
+        }
+
+        res
+    }
+}
+
+/// Converts a seed passed to `new Random(seed)` to the internal seed of the LCG, and back.
+pub fn scramble(seed: i64) -> i64 {
+    (seed ^ MULTIPLIER) & MASK
+}
+
+/// Advances the internal seed `seed` by `steps` calls to `next`. Negative values step backward.
+pub fn advance(seed: i64, steps: i64) -> i64 {
+    Lcg::JAVA.combine_n(steps).next_seed(seed)
+}
+
+/// Returns the internal seed one call to `next` before `seed`.
+pub fn previous(seed: i64) -> i64 {
+    advance(seed, -1)
+}
+
+/// Returns every internal seed for which the next call to `nextInt()` returns `value`.
+///
+/// `nextInt()` only reveals the top 32 bits of the next seed, so there are always `2^16` of them.
+pub fn next_int_inverse(value: i32) -> impl Iterator<Item = i64> {
+    let high = (value as u32 as i64) << 16;
+    let back = Lcg::JAVA.invert();
+
+    (0..1 << 16).map(move |low| back.next_seed(high | low))
+}
+
+/// Returns every internal seed for which the next call to `nextLong()` returns `value`. There are at
+/// most a couple of them, and none for most values.
+pub fn next_long_inverse(value: i64) -> Vec<i64> {
+    // the low half is added as a signed int, borrowing from the high half when negative
+    let low = value as i32;
+    let high = ((value >> 32) as i32).wrapping_add((low < 0) as i32);
+
+    let two = Lcg::JAVA.combine_n(2);
+
+    next_int_inverse(high)
+        .filter(|seed| (two.next_seed(*seed) >> 16) as i32 == low)
+        .collect()
+}
+
+/// Checks if `value` can be returned by `nextLong()`. Only `2^48` out of the `2^64` values can.
+pub fn is_next_long(value: i64) -> bool {
+    !next_long_inverse(value).is_empty()
+}
+
+/// Returns every internal seed for which the next call to `nextFloat()` returns `value`, or `None` if
+/// `value` can never be returned by `nextFloat()`.
+///
+/// `nextFloat()` only reveals the top 24 bits of the next seed, so there are `2^24` of them.
+pub fn next_float_inverse(value: f32) -> Option<impl Iterator<Item = i64>> {
+    let bits = value * (1 << 24) as f32;
+
+    if !(0f32..(1 << 24) as f32).contains(&bits) || bits.fract() != 0f32 {
+        return None;
+    }
+
+    let high = (bits as i64) << 24;
+    let back = Lcg::JAVA.invert();
+
+    Some((0..1 << 24).map(move |low| back.next_seed(high | low)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{advance, is_next_long, next_float_inverse, next_int_inverse, next_long_inverse, scramble, Lcg};
+
+    const SEEDS: [i64; 5] = [0, 1, -1, 2345, -763922862008843532];
+
+    #[test]
+    fn test_advance_matches_java_rand() {
+        for &seed in &SEEDS {
+            for &steps in &[0i64, 1, 2, 17, 1000] {
+                let mut rnd = java_rand::Random::new(seed as u64);
+                for _ in 0..steps {
+                    rnd.next_i32();
+                }
+
+                let internal = advance(scramble(seed), steps);
+                let mut jumped = java_rand::Random::new(scramble(internal) as u64);
+
+                assert_eq!(rnd.next_i64(), jumped.next_i64());
+            }
+        }
+    }
+
+    #[test]
+    fn test_advance_backward() {
+        for &seed in &SEEDS {
+            let internal = scramble(seed);
+
+            assert_eq!(advance(advance(internal, 123_456_789), -123_456_789), internal);
+            assert_eq!(advance(advance(internal, -1), 1), internal);
+        }
+    }
+
+    #[test]
+    fn test_invert() {
+        let inv = Lcg::JAVA.invert();
+
+        assert_eq!(Lcg::JAVA.combine(&inv), Lcg::IDENTITY);
+        assert_eq!(inv.multiplier, 0xDFE05BCB1365);
+    }
+
+    #[test]
+    fn test_next_int_inverse() {
+        for &seed in &SEEDS {
+            let value = java_rand::Random::new(seed as u64).next_i32();
+
+            assert!(next_int_inverse(value).any(|s| s == scramble(seed)));
+        }
+    }
+
+    #[test]
+    fn test_next_long_inverse() {
+        for &seed in &SEEDS {
+            let value = java_rand::Random::new(seed as u64).next_i64();
+
+            assert!(next_long_inverse(value).contains(&scramble(seed)));
+            assert!(is_next_long(value));
+        }
+
+        // most longs are unreachable
+        assert!((0..64).any(|i| !is_next_long(i)));
+    }
+
+    #[test]
+    fn test_next_float_inverse() {
+        let value = java_rand::Random::new(2345).next_f32();
+        let seeds = next_float_inverse(value).unwrap();
+
+        assert!(seeds.into_iter().any(|s| s == scramble(2345)));
+        assert!(next_float_inverse(0.1).is_none());
+        assert!(next_float_inverse(1.0).is_none());
+    }
+}
@@ -1,9 +1,11 @@
-/// This module contains any random utility.
-///
-/// In it, you'll find functions for computing
-/// - slime chunks
-/// - flower type generation
-/// - bedrock generation patterns
+//! This module contains any random utility.
+//!
+//! In it, you'll find functions for computing
+//! - slime chunks
+//! - flower type generation
+//! - bedrock generation patterns
+//!
+//! as well as the random sources (`source`) shared by world generation predictions.
 
 ///
 /// Contains all functions dealing with slime chunks.
@@ -11,7 +13,12 @@
 pub mod slime;
 
 ///
-/// Contains helpers for working with flowers in flower forest biomes.
+/// Contains helpers for jumping ahead, rewinding and inverting `java.util.Random`.
+///
+pub mod lcg;
+
+///
+/// Contains helpers for working with flowers in flower forests and other flower biomes.
 ///
 pub mod flowers;
 
@@ -21,9 +28,22 @@ pub mod flowers;
 /// 
 pub mod bedrock;
 
+///
+/// Contains the noise samplers shared by world generation predictions.
+///
+pub mod noise;
+
+///
+/// Contains the random sources used by modern world generation.
+///
+pub mod source;
+
 // re-exports
 pub use slime::is_slimechunk;
 pub use slime::is_slimechunk_inline;
 
 pub use flowers::flower_at;
 pub use flowers::FlowerForestFlower;
+pub use flowers::flowers_at;
+pub use flowers::Flower;
+pub use flowers::FlowerBiome;
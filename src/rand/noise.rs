@@ -0,0 +1,403 @@
+//! Feature parity with Minecraft's noise samplers, from a single `ImprovedNoise` up to the
+//! `NormalNoise` world generation is built on.
+//!
+//! see: https://github.com/gnembon/fabric-carpet/blob/dd381a84479cd8d5c9e931897d3d06e6768c5c4e/src/main/java/carpet/script/utils/PerlinNoiseSampler.java
+
+use std::array::from_fn;
+
+use crate::util::Point3D;
+
+use super::source::{PositionalRandomFactory, RandomSource};
+
+const GRADIENTS_3D: [[i32; 3]; 16] = [
+    [1, 1, 0],
+    [-1, 1, 0],
+    [1, -1, 0],
+    [-1, -1, 0],
+    [1, 0, 1],
+    [-1, 0, 1],
+    [1, 0, -1],
+    [-1, 0, -1],
+    [0, 1, 1],
+    [0, -1, 1],
+    [0, 1, -1],
+    [0, -1, -1],
+    [1, 1, 0],
+    [0, -1, 1],
+    [-1, 1, 0],
+    [0, -1, -1],
+];
+
+/// The number of random calls needed to create an `ImprovedNoise`, skipped by legacy octaves.
+const SKIP_OCTAVE: u32 = 262;
+
+/// Multiplier applied to the coordinates of the second sampler of a `NormalNoise`.
+const INPUT_FACTOR: f64 = 1.0181268882175227;
+
+/// Coordinates are wrapped around this value to keep the precision of far away samples.
+const ROUND_OFF: f64 = 3.3554432E7;
+
+/// A single octave of perlin noise.
+#[derive(Clone, Debug)]
+pub struct ImprovedNoise {
+    pub origin_x: f64,
+    pub origin_y: f64,
+    pub origin_z: f64,
+    permutations: [u8; 256],
+}
+
+impl ImprovedNoise {
+    pub fn new<R: RandomSource>(rnd: &mut R) -> Self {
+        let origin_x = rnd.next_f64() * 256f64;
+        let origin_y = rnd.next_f64() * 256f64;
+        let origin_z = rnd.next_f64() * 256f64;
+
+        let mut permutations: [u8; 256] = from_fn(|i| i as u8);
+
+        for j in 0..256 {
+            let k = rnd.next_i32_bound(256 - j as i32) as usize;
+            permutations.swap(j, j + k);
+        }
+
+        ImprovedNoise {
+            origin_x,
+            origin_y,
+            origin_z,
+            permutations,
+        }
+    }
+
+    /// The raw value of the noise, as returned by `ImprovedNoise.noise` in the vanilla source.
+    pub fn noise(&self, x: f64, y: f64, z: f64) -> f64 {
+        let f = x + self.origin_x;
+        let g = y + self.origin_y;
+        let h = z + self.origin_z;
+
+        let (i, j, k) = (f.floor(), g.floor(), h.floor());
+        let (l, m, n) = (f - i, g - j, h - k);
+
+        self.sample_and_lerp(i as i32, j as i32, k as i32, l, m, n)
+    }
+
+    fn sample_and_lerp(&self, x: i32, y: i32, z: i32, dx: f64, dy: f64, dz: f64) -> f64 {
+        let i = self.p(x);
+        let j = self.p(x.wrapping_add(1));
+        let k = self.p(i + y);
+        let l = self.p(i + y + 1);
+        let m = self.p(j + y);
+        let n = self.p(j + y + 1);
+
+        let d = grad_dot(self.p(k + z), dx, dy, dz);
+        let e = grad_dot(self.p(m + z), dx - 1f64, dy, dz);
+        let f = grad_dot(self.p(l + z), dx, dy - 1f64, dz);
+        let g = grad_dot(self.p(n + z), dx - 1f64, dy - 1f64, dz);
+        let h = grad_dot(self.p(k + z + 1), dx, dy, dz - 1f64);
+        let o = grad_dot(self.p(m + z + 1), dx - 1f64, dy, dz - 1f64);
+        let p = grad_dot(self.p(l + z + 1), dx, dy - 1f64, dz - 1f64);
+        let q = grad_dot(self.p(n + z + 1), dx - 1f64, dy - 1f64, dz - 1f64);
+
+        let (s, t, u) = (smoothstep(dx), smoothstep(dy), smoothstep(dz));
+
+        lerp(u, lerp2(s, t, d, e, f, g), lerp2(s, t, h, o, p, q))
+    }
+
+    fn p(&self, i: i32) -> i32 {
+        self.permutations[(i & 0xff) as usize] as i32
+    }
+}
+
+/// Two dimensional simplex noise, as `SimplexNoise` in the game. Shapes the outer end islands.
+#[derive(Clone, Debug)]
+pub struct SimplexNoise {
+    permutations: [u8; 256],
+}
+
+const SQRT_3: f64 = 1.7320508075688772;
+const F2: f64 = 0.5 * (SQRT_3 - 1f64);
+const G2: f64 = (3f64 - SQRT_3) / 6f64;
+
+impl SimplexNoise {
+    pub fn new<R: RandomSource>(rnd: &mut R) -> Self {
+        // the origin is rolled but only used by the 3D sampler
+        for _ in 0..3 {
+            rnd.next_f64();
+        }
+
+        let mut permutations: [u8; 256] = from_fn(|i| i as u8);
+
+        for i in 0..256 {
+            let j = rnd.next_i32_bound(256 - i as i32) as usize;
+            permutations.swap(i, i + j);
+        }
+
+        SimplexNoise { permutations }
+    }
+
+    pub fn value_2d(&self, x: f64, y: f64) -> f64 {
+        let d = (x + y) * F2;
+        let i = (x + d).floor() as i32;
+        let j = (y + d).floor() as i32;
+
+        let e = (i + j) as f64 * G2;
+        let h = x - (i as f64 - e);
+        let k = y - (j as f64 - e);
+
+        let (l, m) = if h > k { (1, 0) } else { (0, 1) };
+
+        let n = h - l as f64 + G2;
+        let o = k - m as f64 + G2;
+        let p = h - 1f64 + 2f64 * G2;
+        let q = k - 1f64 + 2f64 * G2;
+
+        let (r, s) = (i & 0xff, j & 0xff);
+
+        let t = self.p(r + self.p(s)) % 12;
+        let u = self.p(r + l + self.p(s + m)) % 12;
+        let v = self.p(r + 1 + self.p(s + 1)) % 12;
+
+        70f64 * (corner_noise(t, h, k) + corner_noise(u, n, o) + corner_noise(v, p, q))
+    }
+
+    fn p(&self, i: i32) -> i32 {
+        self.permutations[(i & 0xff) as usize] as i32
+    }
+}
+
+fn corner_noise(gradient: i32, x: f64, y: f64) -> f64 {
+    let d = 0.5 - x * x - y * y;
+
+    if d < 0f64 {
+        0f64
+    } else {
+        let d = d * d;
+        d * d * grad_dot(gradient, x, y, 0f64)
+    }
+}
+
+fn grad_dot(hash: i32, x: f64, y: f64, z: f64) -> f64 {
+    let grad = GRADIENTS_3D[(hash & 15) as usize];
+
+    grad[0] as f64 * x + grad[1] as f64 * y + grad[2] as f64 * z
+}
+
+fn smoothstep(d: f64) -> f64 {
+    d * d * d * (d * (d * 6f64 - 15f64) + 10f64)
+}
+
+fn lerp(delta: f64, first: f64, second: f64) -> f64 {
+    first + delta * (second - first)
+}
+
+fn lerp2(delta_x: f64, delta_y: f64, d: f64, e: f64, f: f64, g: f64) -> f64 {
+    lerp(delta_y, lerp(delta_x, d, e), lerp(delta_x, f, g))
+}
+
+fn wrap(d: f64) -> f64 {
+    d - (d / ROUND_OFF + 0.5).floor() * ROUND_OFF
+}
+
+/// The octaves of a noise, as in the `worldgen/noise` registry. Octaves with an amplitude of `0.0`
+/// are skipped.
+#[derive(Clone, PartialEq, Debug)]
+pub struct NoiseParameters {
+    pub first_octave: i32,
+    pub amplitudes: Vec<f64>,
+}
+
+impl NoiseParameters {
+    pub fn new(first_octave: i32, amplitudes: &[f64]) -> Self {
+        NoiseParameters {
+            first_octave,
+            amplitudes: amplitudes.to_vec(),
+        }
+    }
+}
+
+/// Several octaves of `ImprovedNoise`, each one twice as fine and half as strong as the previous.
+#[derive(Clone, Debug)]
+pub struct PerlinNoise {
+    levels: Vec<Option<ImprovedNoise>>,
+    amplitudes: Vec<f64>,
+    lowest_freq_input_factor: f64,
+    lowest_freq_value_factor: f64,
+}
+
+impl PerlinNoise {
+    /// Every octave gets its own source, forked off of `rnd` by name.
+    pub fn new<R: RandomSource>(rnd: &mut R, params: &NoiseParameters) -> Self {
+        let factory = rnd.fork_positional();
+
+        let levels = params
+            .amplitudes
+            .iter()
+            .enumerate()
+            .map(|(i, &amp)| {
+                (amp != 0f64).then(|| {
+                    let octave = params.first_octave + i as i32;
+                    ImprovedNoise::new(&mut factory.from_hash_of(&format!("octave_{octave}")))
+                })
+            })
+            .collect();
+
+        PerlinNoise::with_levels(levels, params)
+    }
+
+    /// The octaves are created in sequence from `rnd`, starting with the coarsest one. This is how
+    /// the nether biome noises were seeded before 1.18.
+    pub fn legacy<R: RandomSource>(rnd: &mut R, params: &NoiseParameters) -> Self {
+        let count = params.amplitudes.len() as i32;
+        let first = -params.first_octave;
+
+        assert!(first >= count - 1, "positive octaves are not supported");
+
+        let mut levels: Vec<Option<ImprovedNoise>> = vec![None; count as usize];
+
+        // the octave at frequency 1 always consumes its share of the random
+        let base = ImprovedNoise::new(rnd);
+        if first < count && params.amplitudes[first as usize] != 0f64 {
+            levels[first as usize] = Some(base);
+        }
+
+        for k in (0..first).rev() {
+            if k < count && params.amplitudes[k as usize] != 0f64 {
+                levels[k as usize] = Some(ImprovedNoise::new(rnd));
+            } else {
+                rnd.consume_count(SKIP_OCTAVE);
+            }
+        }
+
+        PerlinNoise::with_levels(levels, params)
+    }
+
+    fn with_levels(levels: Vec<Option<ImprovedNoise>>, params: &NoiseParameters) -> Self {
+        let count = params.amplitudes.len() as i32;
+
+        PerlinNoise {
+            levels,
+            amplitudes: params.amplitudes.clone(),
+            lowest_freq_input_factor: 2f64.powi(params.first_octave),
+            lowest_freq_value_factor: 2f64.powi(count - 1) / (2f64.powi(count) - 1f64),
+        }
+    }
+
+    pub fn value(&self, x: f64, y: f64, z: f64) -> f64 {
+        let mut res = 0f64;
+        let mut input_factor = self.lowest_freq_input_factor;
+        let mut value_factor = self.lowest_freq_value_factor;
+
+        for (level, amp) in self.levels.iter().zip(&self.amplitudes) {
+            if let Some(noise) = level {
+                let v = noise.noise(wrap(x * input_factor), wrap(y * input_factor), wrap(z * input_factor));
+                res += amp * v * value_factor;
+            }
+
+            input_factor *= 2f64;
+            value_factor /= 2f64;
+        }
+
+        res
+    }
+}
+
+/// Feature parity with Minecraft's `NormalNoise`: two `PerlinNoise`s sampled at slightly different
+/// scales and added up. The result is roughly contained in `[-1, 1]`.
+#[derive(Clone, Debug)]
+pub struct NormalNoise {
+    first: PerlinNoise,
+    second: PerlinNoise,
+    value_factor: f64,
+}
+
+impl NormalNoise {
+    pub fn new<R: RandomSource>(rnd: &mut R, params: &NoiseParameters) -> Self {
+        let first = PerlinNoise::new(rnd, params);
+        let second = PerlinNoise::new(rnd, params);
+
+        NormalNoise::with_noises(first, second, params)
+    }
+
+    /// `NormalNoise.createLegacyNetherBiome`
+    pub fn legacy<R: RandomSource>(rnd: &mut R, params: &NoiseParameters) -> Self {
+        let first = PerlinNoise::legacy(rnd, params);
+        let second = PerlinNoise::legacy(rnd, params);
+
+        NormalNoise::with_noises(first, second, params)
+    }
+
+    fn with_noises(first: PerlinNoise, second: PerlinNoise, params: &NoiseParameters) -> Self {
+        let octaves: Vec<usize> = (0..params.amplitudes.len())
+            .filter(|&i| params.amplitudes[i] != 0f64)
+            .collect();
+
+        let span = match (octaves.first(), octaves.last()) {
+            (Some(min), Some(max)) => max - min,
+            _ => 0,
+        };
+
+        NormalNoise {
+            first,
+            second,
+            value_factor: 0.16666666666666666 / expected_deviation(span),
+        }
+    }
+
+    /// Samples the noise at the given point.
+    pub fn sample(&self, p: Point3D<f64>) -> f64 {
+        let a = self.first.value(p.x, p.y, p.z);
+        let b = self
+            .second
+            .value(p.x * INPUT_FACTOR, p.y * INPUT_FACTOR, p.z * INPUT_FACTOR);
+
+        (a + b) * self.value_factor
+    }
+}
+
+fn expected_deviation(octaves: usize) -> f64 {
+    0.1 * (1f64 + 1f64 / (octaves + 1) as f64)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::rand::source::{LegacyRandomSource, RandomSource};
+
+    use crate::util::Point3D;
+
+    use super::{ImprovedNoise, NoiseParameters, NormalNoise, SimplexNoise};
+
+    fn perlin(x: f64, y: f64, z: f64, seed: i64) -> f64 {
+        let noise = ImprovedNoise::new(&mut LegacyRandomSource::new(seed));
+
+        noise.noise(x, y, z) / 2f64 + 0.5
+    }
+
+    #[test]
+    fn test_improved_noise() {
+        assert_eq!(perlin(12.0, 1.0, 16.0, 1337), 0.4209034382230304);
+        assert_eq!(perlin(-3.0, 54.0, 10.0, 1), 0.434455571299763);
+        assert_eq!(perlin(432.0, -43.0, 23.0, 66), 0.37580421601671243);
+        assert_eq!(perlin(-30.0, 120.0, -3130.0, -112), 0.45106062139766767);
+    }
+
+    #[test]
+    fn test_legacy_normal_noise() {
+        let params = NoiseParameters::new(-7, &[1.0, 1.0]);
+        let noise = |seed| NormalNoise::legacy(&mut LegacyRandomSource::new(seed), &params);
+        let p = |x, z| Point3D { x, y: 0.0, z };
+
+        // the nether temperature and humidity of seed 123, pinned from this implementation
+        assert_eq!(noise(123).sample(p(0.0, 0.0)), -0.10494546358119529);
+        assert_eq!(noise(123).sample(p(100.25, -50.5)), 0.013693836896787801);
+        assert_eq!(noise(124).sample(p(-3000.0, 1234.0)), -0.2776789503827537);
+    }
+
+    #[test]
+    fn test_simplex_noise() {
+        // the end island noise of seed 123, pinned from this implementation
+        let mut rnd = LegacyRandomSource::new(123);
+        rnd.consume_count(17292);
+        let noise = SimplexNoise::new(&mut rnd);
+
+        assert_eq!(noise.value_2d(12.5, -3.25), 0.8450163284898612);
+        assert_eq!(noise.value_2d(-100.1, 70.7), -0.7368855553163135);
+    }
+}
@@ -1,3 +1,5 @@
+use super::lcg::{ADDEND, MASK, MULTIPLIER};
+
 /// Determines if a chunk is a slime chunk for a given `seed`, `x` chunk coordinate, and `y` chunk
 /// coordinate.
 ///
@@ -24,11 +26,9 @@ pub fn is_slimechunk_inline(seed: i64, x: i32, z: i32) -> bool {
     let seed = seed ^ 0x3ad8025fi64;
 
     // manual java rand
-    let magic = 0x5DEECE66D;
-    let mask = (1 << 48) - 1;
-    let seed = (seed ^ magic) & mask;
+    let seed = (seed ^ MULTIPLIER) & MASK;
 
-    let seed = seed.wrapping_mul(magic).wrapping_add(0xB) & (mask >> 17 << 17);
+    let seed = seed.wrapping_mul(MULTIPLIER).wrapping_add(ADDEND) & (MASK >> 17 << 17);
     (seed % (10 << 17)) == 0
 }
 
@@ -0,0 +1,179 @@
+use crate::rand::lcg::{scramble, Lcg};
+use crate::util::BlockPoint;
+
+use super::{java_hashcode, next_gaussian, position_seed, PositionalRandomFactory, RandomSource};
+
+/// The `java.util.Random` LCG, as wrapped by the game's `LegacyRandomSource`.
+#[derive(Clone, Debug)]
+pub struct LegacyRandomSource {
+    seed: i64,
+    next_next_gaussian: Option<f64>,
+}
+
+impl LegacyRandomSource {
+    pub fn new(seed: i64) -> Self {
+        LegacyRandomSource {
+            seed: scramble(seed),
+            next_next_gaussian: None,
+        }
+    }
+
+    /// `WorldgenRandom.setLargeFeatureSeed`: the random source of a large feature at chunk (`x`, `z`).
+    pub fn large_feature(seed: i64, x: i32, z: i32) -> Self {
+        let mut rnd = LegacyRandomSource::new(seed);
+        let l = rnd.next_i64();
+        let m = rnd.next_i64();
+
+        LegacyRandomSource::new((x as i64).wrapping_mul(l) ^ (z as i64).wrapping_mul(m) ^ seed)
+    }
+
+    /// `WorldgenRandom.setLargeFeatureWithSalt`: the random source of a structure placement at
+    /// region (`x`, `z`).
+    pub fn large_feature_with_salt(seed: i64, x: i32, z: i32, salt: i32) -> Self {
+        let seed = (x as i64)
+            .wrapping_mul(341873128712)
+            .wrapping_add((z as i64).wrapping_mul(132897987541))
+            .wrapping_add(seed)
+            .wrapping_add(salt as i64);
+
+        LegacyRandomSource::new(seed)
+    }
+
+    /// Returns the next `bits` random bits.
+    pub fn next(&mut self, bits: u32) -> i32 {
+        self.seed = Lcg::JAVA.next_seed(self.seed);
+
+        (self.seed >> (48 - bits)) as i32
+    }
+}
+
+impl RandomSource for LegacyRandomSource {
+    type Positional = LegacyPositionalRandomFactory;
+
+    fn fork(&mut self) -> Self {
+        LegacyRandomSource::new(self.next_i64())
+    }
+
+    fn fork_positional(&mut self) -> Self::Positional {
+        LegacyPositionalRandomFactory { seed: self.next_i64() }
+    }
+
+    fn set_seed(&mut self, seed: i64) {
+        *self = LegacyRandomSource::new(seed);
+    }
+
+    fn next_i32(&mut self) -> i32 {
+        self.next(32)
+    }
+
+    fn next_i32_bound(&mut self, bound: i32) -> i32 {
+        assert!(bound > 0, "bound must be positive");
+
+        // powers of 2 take the high bits
+        if bound & (bound - 1) == 0 {
+            return ((bound as i64 * self.next(31) as i64) >> 31) as i32;
+        }
+
+        loop {
+            let i = self.next(31);
+            let j = i % bound;
+
+            if i.wrapping_sub(j).wrapping_add(bound - 1) >= 0 {
+                return j;
+            }
+        }
+    }
+
+    fn next_i64(&mut self) -> i64 {
+        let i = self.next(32) as i64;
+        let j = self.next(32) as i64;
+
+        (i << 32).wrapping_add(j)
+    }
+
+    fn next_bool(&mut self) -> bool {
+        self.next(1) != 0
+    }
+
+    fn next_f32(&mut self) -> f32 {
+        self.next(24) as f32 * 5.9604645E-8f32
+    }
+
+    fn next_f64(&mut self) -> f64 {
+        let i = self.next(26) as i64;
+        let j = self.next(27) as i64;
+
+        ((i << 27) + j) as f64 * 1.110223E-16f32 as f64
+    }
+
+    fn next_gaussian(&mut self) -> f64 {
+        let mut next_next_gaussian = self.next_next_gaussian.take();
+        let g = next_gaussian(self, &mut next_next_gaussian);
+        self.next_next_gaussian = next_next_gaussian;
+
+        g
+    }
+}
+
+/// Positional factory of a `LegacyRandomSource`. Every source it hands out is seeded by xoring its
+/// input with the factory seed.
+#[derive(Copy, Clone, Debug)]
+pub struct LegacyPositionalRandomFactory {
+    seed: i64,
+}
+
+impl LegacyPositionalRandomFactory {
+    pub fn new(seed: i64) -> Self {
+        LegacyPositionalRandomFactory { seed }
+    }
+}
+
+impl PositionalRandomFactory for LegacyPositionalRandomFactory {
+    type Source = LegacyRandomSource;
+
+    fn at(&self, p: BlockPoint) -> Self::Source {
+        LegacyRandomSource::new(position_seed(p) ^ self.seed)
+    }
+
+    fn from_hash_of(&self, s: &str) -> Self::Source {
+        LegacyRandomSource::new(java_hashcode(s) as i64 ^ self.seed)
+    }
+
+    fn from_seed(&self, seed: i64) -> Self::Source {
+        LegacyRandomSource::new(seed)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::rand::source::RandomSource;
+
+    use super::LegacyRandomSource;
+
+    #[test]
+    fn test_matches_java_rand() {
+        for seed in [0i64, 1, -1, 2345, -763922862008843532] {
+            let mut rnd = LegacyRandomSource::new(seed);
+            let mut java = java_rand::Random::new(seed as u64);
+
+            for bound in 1..200 {
+                assert_eq!(rnd.next_i32(), java.next_i32());
+                assert_eq!(rnd.next_i32_bound(bound), java.next_i32_bound(bound));
+                assert_eq!(rnd.next_i64(), java.next_i64());
+                assert_eq!(rnd.next_bool(), java.next_bool());
+                assert_eq!(rnd.next_f32(), java.next_f32());
+                assert_eq!(rnd.next_f64(), java.next_f64());
+            }
+        }
+    }
+
+    #[test]
+    fn test_gaussian_matches_java_rand() {
+        let mut rnd = LegacyRandomSource::new(42);
+        let mut java = java_rand::Random::new(42);
+
+        for _ in 0..100 {
+            assert!((rnd.next_gaussian() - java.next_gaussian()).abs() < 1e-12);
+        }
+    }
+}
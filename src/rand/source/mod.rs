@@ -0,0 +1,220 @@
+//! Feature parity with Minecraft's `RandomSource` implementations.
+//!
+//! Modern world generation seeds most of its randomness through a `PositionalRandomFactory`,
+//! forked off of the world seed, which hands out a new `RandomSource` for a block position, a
+//! string or a seed.
+//!
+//! see: `net.minecraft.world.level.levelgen.RandomSupport` in the vanilla source
+
+use std::convert::TryInto;
+
+use md5::{Digest, Md5};
+
+use crate::util::BlockPoint;
+
+mod legacy;
+mod xoroshiro;
+
+pub use legacy::{LegacyPositionalRandomFactory, LegacyRandomSource};
+pub use xoroshiro::{Xoroshiro128PlusPlus, XoroshiroPositionalRandomFactory, XoroshiroRandomSource};
+
+pub const GOLDEN_RATIO_64: i64 = -7046029254386353131;
+pub const SILVER_RATIO_64: i64 = 7640891576956012809;
+
+/// Common interface of every random source used by the game.
+pub trait RandomSource {
+    type Positional: PositionalRandomFactory;
+
+    /// Creates a new, independent random source from this one.
+    fn fork(&mut self) -> Self
+    where
+        Self: Sized;
+
+    /// Creates a positional factory from this random source.
+    fn fork_positional(&mut self) -> Self::Positional;
+
+    fn set_seed(&mut self, seed: i64);
+
+    fn next_i32(&mut self) -> i32;
+
+    /// Returns an `i32` in `[0, bound)`. Panics if `bound` is not positive.
+    fn next_i32_bound(&mut self, bound: i32) -> i32;
+
+    fn next_i64(&mut self) -> i64;
+
+    fn next_bool(&mut self) -> bool;
+
+    fn next_f32(&mut self) -> f32;
+
+    fn next_f64(&mut self) -> f64;
+
+    fn next_gaussian(&mut self) -> f64;
+
+    /// Returns an `i32` in `[min, max]`.
+    fn next_i32_between_inclusive(&mut self, min: i32, max: i32) -> i32 {
+        self.next_i32_bound(max - min + 1) + min
+    }
+
+    /// Returns an `i32` in `[origin, bound)`. Panics if `origin >= bound`.
+    fn next_i32_between(&mut self, origin: i32, bound: i32) -> i32 {
+        assert!(origin < bound, "bound - origin is non positive");
+
+        origin + self.next_i32_bound(bound - origin)
+    }
+
+    /// Advances the random source by `count` calls.
+    fn consume_count(&mut self, count: u32) {
+        for _ in 0..count {
+            self.next_i32();
+        }
+    }
+}
+
+/// Hands out random sources derived from a position, a string or a seed.
+// method names follow the vanilla source
+#[allow(clippy::wrong_self_convention)]
+pub trait PositionalRandomFactory {
+    type Source: RandomSource;
+
+    fn at(&self, p: BlockPoint) -> Self::Source;
+
+    fn from_hash_of(&self, s: &str) -> Self::Source;
+
+    fn from_seed(&self, seed: i64) -> Self::Source;
+}
+
+/// A 128 bit seed, as used by `Xoroshiro128PlusPlus`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Seed128 {
+    pub lo: i64,
+    pub hi: i64,
+}
+
+impl Seed128 {
+    pub fn xor(self, lo: i64, hi: i64) -> Self {
+        Seed128 {
+            lo: self.lo ^ lo,
+            hi: self.hi ^ hi,
+        }
+    }
+
+    pub fn mixed(self) -> Self {
+        Seed128 {
+            lo: mix_stafford_13(self.lo),
+            hi: mix_stafford_13(self.hi),
+        }
+    }
+}
+
+/// `RandomSupport.mixStafford13`: David Stafford's 13th variant of the 64 bit finalizer.
+pub fn mix_stafford_13(seed: i64) -> i64 {
+    let seed = seed as u64;
+    let seed = (seed ^ (seed >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    let seed = (seed ^ (seed >> 27)).wrapping_mul(0x94d049bb133111eb);
+
+    (seed ^ (seed >> 31)) as i64
+}
+
+/// Expands a 64 bit seed to a 128 bit one, without mixing it.
+pub fn upgrade_seed_to_128bit_unmixed(seed: i64) -> Seed128 {
+    let lo = seed ^ SILVER_RATIO_64;
+    let hi = lo.wrapping_add(GOLDEN_RATIO_64);
+
+    Seed128 { lo, hi }
+}
+
+/// Expands a 64 bit seed to a 128 bit one, as done when seeding a `XoroshiroRandomSource`.
+pub fn upgrade_seed_to_128bit(seed: i64) -> Seed128 {
+    upgrade_seed_to_128bit_unmixed(seed).mixed()
+}
+
+/// Computes a 128 bit seed from the MD5 hash of `s`.
+pub fn seed_from_hash_of(s: &str) -> Seed128 {
+    let hash = Md5::digest(s.as_bytes());
+
+    let lo = i64::from_be_bytes(hash[0..8].try_into().unwrap());
+    let hi = i64::from_be_bytes(hash[8..16].try_into().unwrap());
+
+    Seed128 { lo, hi }
+}
+
+/// `Mth.getSeed`: the seed of a block position.
+pub fn position_seed(p: BlockPoint) -> i64 {
+    let l = (p.x.wrapping_mul(3129871) as i64) ^ (p.z as i64).wrapping_mul(116129781) ^ (p.y as i64);
+    let l = l.wrapping_mul(l).wrapping_mul(42317861).wrapping_add(l.wrapping_mul(11));
+
+    l >> 16
+}
+
+/// `java.lang.String.hashCode`.
+pub fn java_hashcode(s: &str) -> i32 {
+    s.encode_utf16().fold(0i32, |h, c| h.wrapping_mul(31).wrapping_add(c as i32))
+}
+
+/// `MarsagliaPolarGaussian`: shared by every random source to compute gaussian values.
+fn next_gaussian<R: RandomSource>(rnd: &mut R, next_next_gaussian: &mut Option<f64>) -> f64 {
+    if let Some(g) = next_next_gaussian.take() {
+        return g;
+    }
+
+    loop {
+        let d = 2f64 * rnd.next_f64() - 1f64;
+        let e = 2f64 * rnd.next_f64() - 1f64;
+        let f = d * d + e * e;
+
+        if f < 1f64 && f != 0f64 {
+            let g = (-2f64 * f.ln() / f).sqrt();
+            *next_next_gaussian = Some(e * g);
+
+            return d * g;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::util::Point3D;
+
+    use super::{java_hashcode, mix_stafford_13, position_seed, seed_from_hash_of, upgrade_seed_to_128bit, Seed128};
+
+    #[test]
+    fn test_seed_from_hash_of() {
+        // md5("") = d41d8cd98f00b204e9800998ecf8427e
+        assert_eq!(
+            seed_from_hash_of(""),
+            Seed128 {
+                lo: 0xd41d8cd98f00b204u64 as i64,
+                hi: 0xe9800998ecf8427eu64 as i64
+            }
+        );
+    }
+
+    #[test]
+    fn test_java_hashcode() {
+        assert_eq!(java_hashcode("octave_0"), 1261148513);
+        assert_eq!(java_hashcode("minecraft:offset"), -920384768);
+    }
+
+    #[test]
+    fn test_mix_stafford_13() {
+        assert_eq!(mix_stafford_13(0), 0);
+        assert_eq!(mix_stafford_13(1), 6238072747940578789);
+    }
+
+    #[test]
+    fn test_upgrade_seed() {
+        assert_eq!(
+            upgrade_seed_to_128bit(0),
+            Seed128 {
+                lo: 3847398142028685078,
+                hi: 7192185014346937746
+            }
+        );
+    }
+
+    #[test]
+    fn test_position_seed() {
+        assert_eq!(position_seed(Point3D { x: 0, y: 0, z: 0 }), 0);
+        assert_eq!(position_seed(Point3D { x: 12, y: -64, z: -3000 }), 37724888926741);
+    }
+}
@@ -0,0 +1,227 @@
+use crate::util::BlockPoint;
+
+use super::{
+    next_gaussian, position_seed, seed_from_hash_of, upgrade_seed_to_128bit, PositionalRandomFactory,
+    RandomSource, Seed128, GOLDEN_RATIO_64, SILVER_RATIO_64,
+};
+
+/// The raw `xoroshiro128++` generator.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Xoroshiro128PlusPlus {
+    lo: i64,
+    hi: i64,
+}
+
+impl Xoroshiro128PlusPlus {
+    pub fn new(lo: i64, hi: i64) -> Self {
+        // an all zero state would only ever output zeroes
+        if lo | hi == 0 {
+            return Xoroshiro128PlusPlus {
+                lo: GOLDEN_RATIO_64,
+                hi: SILVER_RATIO_64,
+            };
+        }
+
+        Xoroshiro128PlusPlus { lo, hi }
+    }
+
+    pub fn next_i64(&mut self) -> i64 {
+        let l = self.lo;
+        let m = self.hi;
+        let n = l.wrapping_add(m).rotate_left(17).wrapping_add(l);
+
+        let m = m ^ l;
+        self.lo = l.rotate_left(49) ^ m ^ (m << 21);
+        self.hi = m.rotate_left(28);
+
+        n
+    }
+}
+
+impl From<Seed128> for Xoroshiro128PlusPlus {
+    fn from(value: Seed128) -> Self {
+        Xoroshiro128PlusPlus::new(value.lo, value.hi)
+    }
+}
+
+/// The random source used by modern (1.18+) world generation.
+#[derive(Clone, Debug)]
+pub struct XoroshiroRandomSource {
+    rng: Xoroshiro128PlusPlus,
+    next_next_gaussian: Option<f64>,
+}
+
+impl XoroshiroRandomSource {
+    pub fn new(seed: i64) -> Self {
+        XoroshiroRandomSource::from(upgrade_seed_to_128bit(seed))
+    }
+
+    pub fn from_seeds(lo: i64, hi: i64) -> Self {
+        XoroshiroRandomSource {
+            rng: Xoroshiro128PlusPlus::new(lo, hi),
+            next_next_gaussian: None,
+        }
+    }
+
+    /// Returns the next `bits` random bits, taken from the high bits of the next `i64`.
+    fn next_bits(&mut self, bits: u32) -> i64 {
+        ((self.rng.next_i64() as u64) >> (64 - bits)) as i64
+    }
+}
+
+impl From<Seed128> for XoroshiroRandomSource {
+    fn from(value: Seed128) -> Self {
+        XoroshiroRandomSource::from_seeds(value.lo, value.hi)
+    }
+}
+
+impl RandomSource for XoroshiroRandomSource {
+    type Positional = XoroshiroPositionalRandomFactory;
+
+    fn fork(&mut self) -> Self {
+        let lo = self.rng.next_i64();
+        let hi = self.rng.next_i64();
+
+        XoroshiroRandomSource::from_seeds(lo, hi)
+    }
+
+    fn fork_positional(&mut self) -> Self::Positional {
+        let lo = self.rng.next_i64();
+        let hi = self.rng.next_i64();
+
+        XoroshiroPositionalRandomFactory { lo, hi }
+    }
+
+    fn set_seed(&mut self, seed: i64) {
+        *self = XoroshiroRandomSource::new(seed);
+    }
+
+    fn next_i32(&mut self) -> i32 {
+        self.rng.next_i64() as i32
+    }
+
+    fn next_i32_bound(&mut self, bound: i32) -> i32 {
+        assert!(bound > 0, "bound must be positive");
+
+        // Lemire's nearly divisionless method
+        let bound = bound as u64;
+        let mut m = (self.next_i32() as u32 as u64) * bound;
+        let mut n = m & 0xffffffff;
+
+        if n < bound {
+            let i = (bound as u32).wrapping_neg() % bound as u32;
+
+            while n < i as u64 {
+                m = (self.next_i32() as u32 as u64) * bound;
+                n = m & 0xffffffff;
+            }
+        }
+
+        (m >> 32) as i32
+    }
+
+    fn next_i64(&mut self) -> i64 {
+        self.rng.next_i64()
+    }
+
+    fn next_bool(&mut self) -> bool {
+        self.rng.next_i64() & 1 != 0
+    }
+
+    fn next_f32(&mut self) -> f32 {
+        self.next_bits(24) as f32 * 5.9604645E-8f32
+    }
+
+    fn next_f64(&mut self) -> f64 {
+        self.next_bits(53) as f64 * 1.110223E-16f32 as f64
+    }
+
+    fn next_gaussian(&mut self) -> f64 {
+        let mut next_next_gaussian = self.next_next_gaussian.take();
+        let g = next_gaussian(self, &mut next_next_gaussian);
+        self.next_next_gaussian = next_next_gaussian;
+
+        g
+    }
+
+    fn consume_count(&mut self, count: u32) {
+        for _ in 0..count {
+            self.rng.next_i64();
+        }
+    }
+}
+
+/// Positional factory of a `XoroshiroRandomSource`.
+#[derive(Copy, Clone, Debug)]
+pub struct XoroshiroPositionalRandomFactory {
+    lo: i64,
+    hi: i64,
+}
+
+impl XoroshiroPositionalRandomFactory {
+    pub fn new(lo: i64, hi: i64) -> Self {
+        XoroshiroPositionalRandomFactory { lo, hi }
+    }
+}
+
+impl PositionalRandomFactory for XoroshiroPositionalRandomFactory {
+    type Source = XoroshiroRandomSource;
+
+    fn at(&self, p: BlockPoint) -> Self::Source {
+        XoroshiroRandomSource::from_seeds(position_seed(p) ^ self.lo, self.hi)
+    }
+
+    fn from_hash_of(&self, s: &str) -> Self::Source {
+        seed_from_hash_of(s).xor(self.lo, self.hi).into()
+    }
+
+    fn from_seed(&self, seed: i64) -> Self::Source {
+        XoroshiroRandomSource::from_seeds(seed ^ self.lo, seed ^ self.hi)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::rand::source::{PositionalRandomFactory, RandomSource};
+    use crate::util::Point3D;
+
+    use super::{Xoroshiro128PlusPlus, XoroshiroRandomSource};
+
+    #[test]
+    fn test_xoroshiro_next_i64() {
+        let mut rng = Xoroshiro128PlusPlus::new(1, 2);
+
+        assert_eq!(rng.next_i64(), 393217);
+        assert_eq!(rng.next_i64(), 669327710093319);
+        assert_eq!(rng.next_i64(), 1732421326133921491);
+    }
+
+    #[test]
+    fn test_xoroshiro_source() {
+        let mut rnd = XoroshiroRandomSource::new(42);
+        let expected = [
+            (0, 678722133),
+            (32, 1035403458),
+            (49, 615112859),
+            (104, 876413491),
+            (109, 1614283818),
+        ];
+
+        for (i, &(small, large)) in expected.iter().enumerate() {
+            assert_eq!(rnd.next_i32_bound(i as i32 * 37 + 1), small);
+            assert_eq!(rnd.next_i32_bound(i32::MAX - i as i32), large);
+        }
+
+        assert_eq!(rnd.next_f32(), 0.4990607);
+        assert_eq!(rnd.next_f64(), 0.4922907789978952);
+        assert_eq!(rnd.next_i64(), 1714950531046216814);
+    }
+
+    #[test]
+    fn test_xoroshiro_positional() {
+        let factory = XoroshiroRandomSource::new(42).fork_positional();
+
+        assert_eq!(factory.at(Point3D { x: 100, y: 64, z: -200 }).next_i64(), 4363513876100204293);
+        assert_eq!(factory.from_hash_of("minecraft:test").next_i64(), -3016813341981127063);
+    }
+}
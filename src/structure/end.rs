@@ -0,0 +1,293 @@
+//! End cities, end gateways and the pillars of the main island.
+
+use crate::biome::{Biome, EndBiomeSource};
+use crate::rand::source::{LegacyRandomSource, RandomSource};
+use crate::util::{BlockPoint, ChunkPoint, Point3D, Version};
+
+use super::StructureSet;
+
+/// The number of outer end gateways, one is spawned for every dragon killed.
+pub const GATEWAY_COUNT: usize = 20;
+
+/// The distance of the outer end gateways from the center of the main island.
+pub const GATEWAY_DISTANCE: f64 = 96.0;
+
+/// The height of the outer end gateways.
+pub const GATEWAY_Y: i32 = 75;
+
+/// How far out the game starts looking for land when a player enters an outer end gateway.
+pub const EXIT_SEARCH_DISTANCE: f64 = 1024.0;
+
+/// How many steps of 16 blocks the game takes in each direction while looking for land.
+pub const EXIT_SEARCH_STEPS: i32 = 16;
+
+/// The number of obsidian pillars around the main island.
+pub const SPIKE_COUNT: usize = 10;
+
+/// The distance of the pillars from the center of the main island.
+pub const SPIKE_DISTANCE: f64 = 42.0;
+
+// the main island, where no end city can generate, spans this many chunks around the origin
+const MAIN_ISLAND_CHUNKS: i64 = 64;
+
+/// Returns the end city attempts in the rectangular area outlined by `start` and `end`.
+///
+/// Attempts in the biome of the main island are left out. The height of the terrain and the
+/// biomes of the outer islands are not checked.
+pub fn end_cities(seed: i64, start: ChunkPoint, end: ChunkPoint, version: Version) -> Vec<ChunkPoint> {
+    let placement = match StructureSet::EndCity.placement(version) {
+        Some(placement) => placement,
+        None => return Vec::new(),
+    };
+
+    placement
+        .structure_chunks(seed, start, end)
+        .into_iter()
+        .filter(|c| !is_main_island(*c))
+        .collect()
+}
+
+/// Returns the end city attempts of `end_cities` which lie in end highlands or end midlands, the
+/// only biomes end cities generate in since 1.13. The height of the terrain is still not checked.
+pub fn valid_end_cities(seed: i64, start: ChunkPoint, end: ChunkPoint, version: Version) -> Vec<ChunkPoint> {
+    let source = EndBiomeSource::new(seed, version);
+
+    end_cities(seed, start, end, version)
+        .into_iter()
+        .filter(|c| matches!(source.biome_at_chunk(*c), Biome::EndHighlands | Biome::EndMidlands))
+        .collect()
+}
+
+fn is_main_island(c: ChunkPoint) -> bool {
+    let (x, z) = (c.x as i64, c.z as i64);
+
+    x * x + z * z <= MAIN_ISLAND_CHUNKS * MAIN_ISLAND_CHUNKS
+}
+
+/// Returns the outer end gateways of a `seed`, in the order the dragon kills spawn them.
+pub fn end_gateways(seed: i64) -> Vec<BlockPoint> {
+    let mut gateways: Vec<usize> = (0..GATEWAY_COUNT).collect();
+    let mut rnd = LegacyRandomSource::new(seed);
+
+    // `Collections.shuffle`
+    for i in (2..=GATEWAY_COUNT).rev() {
+        let j = rnd.next_i32_bound(i as i32) as usize;
+        gateways.swap(i - 1, j);
+    }
+
+    // the gateways are taken from the back of the list
+    gateways.into_iter().rev().map(gateway_pos).collect()
+}
+
+/// The position of the gateway with the index `i` on the circle around the main island.
+fn gateway_pos(i: usize) -> BlockPoint {
+    let angle = 2.0 * (-std::f64::consts::PI + 0.15707963267948966 * i as f64);
+
+    Point3D {
+        x: (GATEWAY_DISTANCE * angle.cos()).floor() as i32,
+        y: GATEWAY_Y,
+        z: (GATEWAY_DISTANCE * angle.sin()).floor() as i32,
+    }
+}
+
+/// Returns the chunk holding the exit of the outer end gateway `gateway` of a `seed`, as
+/// `findExitPosition` in the game.
+///
+/// The game starts `EXIT_SEARCH_DISTANCE` blocks out on the ray from the origin through the
+/// gateway, steps back by 16 blocks while the chunk has terrain, then forward while it is empty, up
+/// to `EXIT_SEARCH_STEPS` steps each. The terrain is not generated here: a chunk counts as empty
+/// when its `EndBiomeSource::island_height` is negative, so the chunk is an approximation. The exit
+/// block inside of it is not computed.
+pub fn exit_gateway_estimate(seed: i64, gateway: BlockPoint) -> ChunkPoint {
+    // the island heights are the same in every version
+    let source = EndBiomeSource::new(seed, Version::V1_13);
+
+    let (x, z) = (gateway.x as f64, gateway.z as f64);
+    let len = (x * x + z * z).sqrt();
+    let (dx, dz) = (x / len, z / len);
+
+    let chunk = |(x, z): (f64, f64)| ChunkPoint {
+        x: (x / 16.0).floor() as i32,
+        z: (z / 16.0).floor() as i32,
+    };
+    let is_empty = |p| source.island_height(chunk(p)) < 0.0;
+
+    let mut p = (dx * EXIT_SEARCH_DISTANCE, dz * EXIT_SEARCH_DISTANCE);
+
+    let mut steps = EXIT_SEARCH_STEPS;
+    while !is_empty(p) && steps > 0 {
+        p = (p.0 + dx * -16.0, p.1 + dz * -16.0);
+        steps -= 1;
+    }
+
+    let mut steps = EXIT_SEARCH_STEPS;
+    while is_empty(p) && steps > 0 {
+        p = (p.0 + dx * 16.0, p.1 + dz * 16.0);
+        steps -= 1;
+    }
+
+    chunk(p)
+}
+
+/// One of the obsidian pillars around the main island, as `EndSpike` in the game.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct EndSpike {
+    pub center_x: i32,
+    pub center_z: i32,
+    pub radius: i32,
+
+    /// The height of the bedrock block the end crystal sits on, obsidian fills the pillar below it.
+    pub height: i32,
+
+    /// Whether the end crystal is caged by iron bars.
+    pub guarded: bool,
+}
+
+impl EndSpike {
+    /// The top obsidian block in the center of the pillar, right below the bedrock at `height`.
+    pub fn top(&self) -> BlockPoint {
+        Point3D {
+            x: self.center_x,
+            y: self.height - 1,
+            z: self.center_z,
+        }
+    }
+}
+
+/// Returns the `SPIKE_COUNT` pillars of a `seed`, counter clockwise starting from the east.
+pub fn end_spikes(seed: i64) -> Vec<EndSpike> {
+    // only 65536 different arrangements exist
+    let spike_seed = java_rand::Random::new(seed as u64).next_i64() & 0xffff;
+    let mut rnd = java_rand::Random::new(spike_seed as u64);
+
+    // `Collections.shuffle`
+    let mut sizes: Vec<i32> = (0..SPIKE_COUNT as i32).collect();
+    for i in (2..=SPIKE_COUNT).rev() {
+        let j = rnd.next_i32_bound(i as i32) as usize;
+        sizes.swap(i - 1, j);
+    }
+
+    sizes
+        .into_iter()
+        .enumerate()
+        .map(|(i, size)| {
+            let angle = 2.0 * (-std::f64::consts::PI + std::f64::consts::PI / 10.0 * i as f64);
+
+            EndSpike {
+                center_x: (SPIKE_DISTANCE * angle.cos()).floor() as i32,
+                center_z: (SPIKE_DISTANCE * angle.sin()).floor() as i32,
+                radius: 2 + size / 3,
+                height: 76 + size * 3,
+                guarded: size == 1 || size == 2,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::structure::StructureSet;
+    use crate::util::{ChunkPoint, Point3D, Version};
+
+    use crate::biome::{Biome, EndBiomeSource};
+
+    use super::{
+        end_cities, end_gateways, end_spikes, exit_gateway_estimate, valid_end_cities, EndSpike, GATEWAY_COUNT,
+        SPIKE_COUNT,
+    };
+
+    #[test]
+    fn test_end_gateways() {
+        // pinned from this implementation
+        let gateways = end_gateways(123);
+        assert_eq!(gateways.len(), GATEWAY_COUNT);
+        assert_eq!(gateways[0], Point3D { x: 77, y: 75, z: 56 });
+        assert_eq!(gateways[1], Point3D { x: -78, y: 75, z: -57 });
+        assert_eq!(gateways[2], Point3D { x: 91, y: 75, z: -30 });
+
+        let gateways = end_gateways(-4172144997902289642);
+        assert_eq!(gateways[0], Point3D { x: -57, y: 75, z: -78 });
+
+        // every gateway is spawned once
+        let mut sorted = gateways.clone();
+        sorted.sort_by_key(|p| (p.x, p.z));
+        sorted.dedup();
+        assert_eq!(sorted.len(), GATEWAY_COUNT);
+    }
+
+    #[test]
+    fn test_exit_gateway_estimate() {
+        let source = EndBiomeSource::new(123, Version::V1_20);
+        let gateways = end_gateways(123);
+
+        // pinned from this implementation
+        assert_eq!(exit_gateway_estimate(123, gateways[0]), ChunkPoint { x: 56, z: 41 });
+        assert_eq!(exit_gateway_estimate(123, gateways[1]), ChunkPoint { x: -59, z: -44 });
+
+        // no land within 1280 blocks to the east, the search gives up after 16 steps forward
+        let east = exit_gateway_estimate(123, Point3D { x: 96, y: 75, z: 0 });
+        assert_eq!(east, ChunkPoint { x: 80, z: 0 });
+        assert!(source.island_height(east) < 0.0);
+
+        for gateway in gateways {
+            let exit = exit_gateway_estimate(123, gateway);
+            let dist = ((exit.x * 16) as f64).hypot((exit.z * 16) as f64);
+
+            // on the ray through the gateway, on land unless the search gave up
+            assert!(exit.x * gateway.x >= 0 && exit.z * gateway.z >= 0);
+            assert!(dist > 1024.0 - 16.0 * 17.0 && dist < 1024.0 + 16.0 * 17.0);
+            assert!(source.island_height(exit) >= 0.0 || dist > 1024.0 + 16.0 * 15.0);
+        }
+    }
+
+    #[test]
+    fn test_end_cities() {
+        let placement = StructureSet::EndCity.placement(Version::V1_20).unwrap();
+
+        // pinned from this implementation
+        assert_eq!(placement.potential_chunk(123, 1, 0).x, 24);
+
+        let start = ChunkPoint { x: -200, z: -200 };
+        let end = ChunkPoint { x: 199, z: 199 };
+        let cities = end_cities(123, start, end, Version::V1_20);
+
+        assert!(!cities.is_empty());
+        assert!(cities.iter().all(|c| c.x * c.x + c.z * c.z > 64 * 64));
+        assert!(cities.len() < placement.structure_chunks(123, start, end).len());
+
+        // only the cities on highlands and midlands are left
+        let source = EndBiomeSource::new(123, Version::V1_20);
+        let valid = valid_end_cities(123, start, end, Version::V1_20);
+        assert!(!valid.is_empty() && valid.len() < cities.len());
+        assert!(valid
+            .iter()
+            .all(|c| matches!(source.biome_at_chunk(*c), Biome::EndHighlands | Biome::EndMidlands)));
+
+        // no end cities before 1.13
+        assert!(valid_end_cities(123, start, end, Version::V1_12).is_empty());
+    }
+
+    #[test]
+    fn test_end_spikes() {
+        let spikes = end_spikes(123);
+        let spike = |center_x, center_z, radius, height, guarded| EndSpike {
+            center_x,
+            center_z,
+            radius,
+            height,
+            guarded,
+        };
+
+        // pinned from this implementation
+        assert_eq!(spikes.len(), SPIKE_COUNT);
+        assert_eq!(spikes[0], spike(42, 0, 3, 88, false));
+        assert_eq!(spikes[5], spike(-42, -1, 4, 97, false));
+        assert_eq!(spikes[7], spike(-13, -40, 2, 82, true));
+        assert_eq!(spikes[9], spike(33, -25, 2, 79, true));
+        assert_eq!(spikes[6].top(), Point3D { x: -34, y: 102, z: -25 });
+
+        // every seed has the same two caged pillars, in different places
+        assert_eq!(spikes.iter().filter(|s| s.guarded).count(), 2);
+        assert_eq!(spikes.iter().map(|s| s.height).max(), Some(103));
+    }
+}
@@ -0,0 +1,165 @@
+//! This module predicts where structures are attempted in a world.
+//!
+//! Mostly the placement of the attempts is computed: whether the biome at an attempt allows the
+//! structure to generate is only checked for nether structures and end cities.
+
+mod placement;
+pub mod end;
+pub mod nether;
+pub mod ocean;
+pub mod stronghold;
+
+pub use placement::{FrequencyReduction, RandomSpreadPlacement, SpreadType};
+
+use crate::util::{Point3D, Version};
+
+/// Every structure set placed on a region grid, named after the vanilla `structure_set`s.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum StructureSet {
+    Village,
+    DesertPyramid,
+    Igloo,
+    JungleTemple,
+    SwampHut,
+    PillagerOutpost,
+    OceanMonument,
+    WoodlandMansion,
+    AncientCity,
+    RuinedPortal,
+    Shipwreck,
+    OceanRuin,
+    TrailRuins,
+    TrialChambers,
+    BuriedTreasure,
+    Mineshaft,
+    EndCity,
+    NetherFossil,
+
+    /// Nether fortresses and bastion remnants share a single grid.
+    NetherComplex,
+}
+
+impl StructureSet {
+    /// Returns the placement of the structure set in a given version, or `None` if the set does not
+    /// exist in that version or is not placed on a region grid. Versions prior to 1.13 are not
+    /// supported.
+    pub fn placement(&self, version: Version) -> Option<RandomSpreadPlacement> {
+        use StructureSet::*;
+
+        if version < Version::V1_13 {
+            return None;
+        }
+
+        let placement = match self {
+            Village if version < Version::V1_18 => RandomSpreadPlacement::new(32, 8, 10387312),
+            Village => RandomSpreadPlacement::new(34, 8, 10387312),
+            DesertPyramid => RandomSpreadPlacement::new(32, 8, 14357617),
+            Igloo => RandomSpreadPlacement::new(32, 8, 14357618),
+            JungleTemple => RandomSpreadPlacement::new(32, 8, 14357619),
+            SwampHut => RandomSpreadPlacement::new(32, 8, 14357620),
+            PillagerOutpost if version < Version::V1_14 => return None,
+            PillagerOutpost => RandomSpreadPlacement::new(32, 8, 165745296)
+                .with_frequency(0.2, FrequencyReduction::LegacyType1),
+            OceanMonument => RandomSpreadPlacement::new(32, 5, 10387313).with_spread(SpreadType::Triangular),
+            WoodlandMansion => RandomSpreadPlacement::new(80, 20, 10387319).with_spread(SpreadType::Triangular),
+            AncientCity if version < Version::V1_19 => return None,
+            AncientCity => RandomSpreadPlacement::new(24, 8, 20083232),
+            RuinedPortal if version < Version::V1_16 => return None,
+            RuinedPortal => RandomSpreadPlacement::new(40, 15, 34222645),
+            Shipwreck if version < Version::V1_16 => RandomSpreadPlacement::new(16, 8, 165745295),
+            Shipwreck => RandomSpreadPlacement::new(24, 4, 165745295),
+            OceanRuin if version < Version::V1_16 => RandomSpreadPlacement::new(16, 8, 14357621),
+            OceanRuin => RandomSpreadPlacement::new(20, 8, 14357621),
+            TrailRuins if version < Version::V1_20 => return None,
+            TrailRuins => RandomSpreadPlacement::new(34, 8, 83469867),
+            TrialChambers if version < Version::V1_21 => return None,
+            TrialChambers => RandomSpreadPlacement::new(34, 12, 94251327),
+            BuriedTreasure => RandomSpreadPlacement::new(1, 0, 0)
+                .with_frequency(0.01, FrequencyReduction::LegacyType2)
+                .with_locate_offset(Point3D { x: 9, y: 0, z: 9 }),
+            Mineshaft => RandomSpreadPlacement::new(1, 0, 0).with_frequency(0.004, FrequencyReduction::LegacyType3),
+            EndCity => RandomSpreadPlacement::new(20, 11, 10387313).with_spread(SpreadType::Triangular),
+            NetherFossil if version < Version::V1_16 => return None,
+            NetherFossil => RandomSpreadPlacement::new(2, 1, 14357921),
+            NetherComplex if version < Version::V1_16 => return None,
+            NetherComplex => RandomSpreadPlacement::new(27, 4, 30084232),
+        };
+
+        Some(placement)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::rand::source::{LegacyRandomSource, RandomSource};
+    use crate::util::{ChunkPoint, Version};
+
+    use super::StructureSet;
+
+    #[test]
+    fn test_large_feature_with_salt() {
+        let mut rnd = LegacyRandomSource::large_feature_with_salt(1, 2, 3, 4);
+        let mut java = java_rand::Random::new((2i64 * 341873128712 + 3 * 132897987541 + 1 + 4) as u64);
+
+        assert_eq!(rnd.next_i64(), java.next_i64());
+    }
+
+    #[test]
+    fn test_potential_chunk() {
+        let huts = StructureSet::SwampHut.placement(Version::V1_20).unwrap();
+        let mansions = StructureSet::WoodlandMansion.placement(Version::V1_20).unwrap();
+
+        // pinned from this implementation
+        assert_eq!(huts.potential_chunk(123, -1, 2), ChunkPoint { x: -11, z: 87 });
+        assert_eq!(mansions.potential_chunk(-4172144997902289642, 3, -7), ChunkPoint { x: 251, z: -542 });
+    }
+
+    #[test]
+    fn test_placement_chunks() {
+        let seed = -763922862008843532;
+        let villages = StructureSet::Village.placement(Version::V1_18).unwrap();
+
+        for rx in -5..5 {
+            for rz in -5..5 {
+                let c = villages.potential_chunk(seed, rx, rz);
+
+                assert_eq!(villages.region(c), (rx, rz));
+                assert!(c.x - rx * 34 < 34 - 8 && c.z - rz * 34 < 34 - 8);
+                assert!(villages.is_structure_chunk(seed, c));
+            }
+        }
+
+        let start = ChunkPoint { x: -170, z: -170 };
+        let end = ChunkPoint { x: 169, z: 169 };
+        assert_eq!(villages.structure_chunks(seed, start, end).len(), 100);
+    }
+
+    #[test]
+    fn test_frequency_reduction() {
+        let seed = 123;
+        let treasures = StructureSet::BuriedTreasure.placement(Version::V1_20).unwrap();
+        let start = ChunkPoint { x: -100, z: -100 };
+        let end = ChunkPoint { x: 99, z: 99 };
+
+        let chunks = treasures.structure_chunks(seed, start, end);
+
+        // pinned from this implementation, roughly 1% of the chunks hold a treasure
+        assert_eq!(chunks.len(), 444);
+        assert_eq!(chunks[0], ChunkPoint { x: -100, z: -21 });
+    }
+
+    #[test]
+    fn test_versions() {
+        assert!(StructureSet::AncientCity.placement(Version::V1_18).is_none());
+        assert!(StructureSet::AncientCity.placement(Version::V1_19).is_some());
+        assert!(StructureSet::SwampHut.placement(Version::V1_12).is_none());
+        assert_eq!(StructureSet::Village.placement(Version::V1_16).unwrap().spacing, 32);
+
+        // shipwrecks and ocean ruins were spread out in 1.16
+        let wrecks = StructureSet::Shipwreck.placement(Version::V1_14).unwrap();
+        assert_eq!((wrecks.spacing, wrecks.separation), (16, 8));
+        assert_eq!(StructureSet::Shipwreck.placement(Version::V1_16).unwrap().spacing, 24);
+        assert_eq!(StructureSet::OceanRuin.placement(Version::V1_14).unwrap().spacing, 16);
+        assert_eq!(StructureSet::OceanRuin.placement(Version::V1_16).unwrap().spacing, 20);
+    }
+}
@@ -0,0 +1,363 @@
+//! Nether fortresses and bastion remnants.
+//!
+//! From 1.16 on both share the `NetherComplex` grid, and a weighted pick decides which one is
+//! attempted in each region. Before that, nether fortresses had a grid of their own.
+
+use crate::biome::{Biome, BiomeSource, NetherBiomeSource};
+use crate::rand::source::{LegacyRandomSource, RandomSource};
+use crate::util::{BlockPoint, ChunkPoint, Version};
+
+use super::{RandomSpreadPlacement, StructureSet};
+
+// the weights of the nether complexes, in the order the game rolls them
+const FORTRESS_WEIGHT: i32 = 2;
+const BASTION_WEIGHT: i32 = 3;
+
+// prior to 1.16, only a third of the 16x16 chunk regions hold a fortress
+const LEGACY_SPACING: i32 = 16;
+const LEGACY_CHANCE: i32 = 3;
+
+// nearest_fortress gives up after searching this many rings of regions
+const MAX_RINGS: i32 = 256;
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum NetherStructure {
+    Fortress,
+    Bastion,
+}
+
+/// The structure attempted in a region of the nether grid.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct NetherStart {
+    pub structure: NetherStructure,
+    pub chunk: ChunkPoint,
+}
+
+impl NetherStart {
+    /// The block reported by `/locate`.
+    pub fn pos(&self) -> BlockPoint {
+        self.chunk.into()
+    }
+}
+
+/// Returns the structure attempted in region (`rx`, `rz`), or `None` if the region is empty.
+///
+/// Biomes are not checked, see `check_biome`.
+pub fn region_start(seed: i64, rx: i32, rz: i32, version: Version) -> Option<NetherStart> {
+    match placement(version) {
+        Some(placement) => {
+            let chunk = placement.potential_chunk(seed, rx, rz);
+            let structure = if version >= Version::V1_18 {
+                pick(seed, chunk)
+            } else {
+                legacy_pick(seed, rx, rz, placement)
+            };
+
+            Some(NetherStart { structure, chunk })
+        }
+        None => legacy_start(seed, rx, rz),
+    }
+}
+
+/// Returns every structure attempted in the rectangular area outlined by `start` and `end`.
+pub fn starts(seed: i64, start: ChunkPoint, end: ChunkPoint, version: Version) -> Vec<NetherStart> {
+    assert!(start.x <= end.x);
+    assert!(start.z <= end.z);
+
+    let (rx0, rz0) = region(start, version);
+    let (rx1, rz1) = region(end, version);
+
+    (rx0..=rx1)
+        .flat_map(|rx| (rz0..=rz1).map(move |rz| (rx, rz)))
+        .filter_map(|(rx, rz)| region_start(seed, rx, rz, version))
+        .filter(|s| start.x <= s.chunk.x && s.chunk.x <= end.x && start.z <= s.chunk.z && s.chunk.z <= end.z)
+        .collect()
+}
+
+/// Returns the structure which generates for the attempt `start`, or `None` if its biome doesn't
+/// allow it.
+///
+/// Bastions can't generate in basalt deltas. From 1.18.2 on, the game then tries a fortress in the
+/// same chunk instead. Fortresses generate in every nether biome.
+pub fn check_biome(source: &NetherBiomeSource, start: NetherStart, version: Version) -> Option<NetherStart> {
+    if start.structure == NetherStructure::Fortress {
+        return Some(start);
+    }
+
+    // the jigsaw start of the bastion, the center of the chunk before 1.18
+    let (qx, qz) = if version >= Version::V1_18 {
+        (start.chunk.x * 4, start.chunk.z * 4)
+    } else {
+        (start.chunk.x * 4 + 2, start.chunk.z * 4 + 2)
+    };
+
+    if source.biome_at_quart(qx, 0, qz) != Biome::BasaltDeltas {
+        Some(start)
+    } else if version >= Version::V1_18_2 {
+        Some(NetherStart {
+            structure: NetherStructure::Fortress,
+            ..start
+        })
+    } else {
+        None
+    }
+}
+
+/// Returns the structures generating in the rectangular area outlined by `start` and `end`, once
+/// biomes are taken into account.
+pub fn valid_starts(seed: i64, start: ChunkPoint, end: ChunkPoint, version: Version) -> Vec<NetherStart> {
+    let source = NetherBiomeSource::new(seed, version);
+
+    starts(seed, start, end, version)
+        .into_iter()
+        .filter_map(|s| check_biome(&source, s, version))
+        .collect()
+}
+
+/// Returns the fortress closest to the nether block `p`.
+///
+/// Bastion attempts which turn into fortresses because of their biome are not considered.
+pub fn nearest_fortress(seed: i64, p: BlockPoint, version: Version) -> Option<NetherStart> {
+    let (rx, rz) = region(p.into(), version);
+    let region_blocks = spacing(version) as f64 * 16.0;
+
+    let dist = |s: &NetherStart| {
+        let q = s.pos();
+        ((q.x - p.x) as f64).hypot((q.z - p.z) as f64)
+    };
+
+    let mut best: Option<(NetherStart, f64)> = None;
+
+    for ring in 0..MAX_RINGS {
+        // everything from here on is further away than the best fortress so far
+        if let Some((_, d)) = best {
+            if (ring - 1) as f64 * region_blocks > d {
+                break;
+            }
+        }
+
+        for (dx, dz) in ring_offsets(ring) {
+            let start = match region_start(seed, rx + dx, rz + dz, version) {
+                Some(s) if s.structure == NetherStructure::Fortress => s,
+                _ => continue,
+            };
+
+            let d = dist(&start);
+            if best.is_none_or(|(_, b)| d < b) {
+                best = Some((start, d));
+            }
+        }
+    }
+
+    best.map(|(s, _)| s)
+}
+
+fn placement(version: Version) -> Option<RandomSpreadPlacement> {
+    StructureSet::NetherComplex.placement(version)
+}
+
+fn spacing(version: Version) -> i32 {
+    placement(version).map_or(LEGACY_SPACING, |p| p.spacing)
+}
+
+fn region(c: ChunkPoint, version: Version) -> (i32, i32) {
+    let spacing = spacing(version);
+
+    (c.x.div_euclid(spacing), c.z.div_euclid(spacing))
+}
+
+/// The regions `ring` regions away from the center.
+fn ring_offsets(ring: i32) -> Vec<(i32, i32)> {
+    (-ring..=ring)
+        .flat_map(|dx| (-ring..=ring).map(move |dz| (dx, dz)))
+        .filter(|(dx, dz)| dx.abs().max(dz.abs()) == ring)
+        .collect()
+}
+
+/// The weighted pick between the structures of the set, from 1.18 on.
+fn pick(seed: i64, c: ChunkPoint) -> NetherStructure {
+    let mut rnd = LegacyRandomSource::large_feature(seed, c.x, c.z);
+
+    if rnd.next_i32_bound(FORTRESS_WEIGHT + BASTION_WEIGHT) < FORTRESS_WEIGHT {
+        NetherStructure::Fortress
+    } else {
+        NetherStructure::Bastion
+    }
+}
+
+/// Before 1.18 each structure rolls on the random which just placed the attempt of its region.
+fn legacy_pick(seed: i64, rx: i32, rz: i32, placement: RandomSpreadPlacement) -> NetherStructure {
+    let mut rnd = LegacyRandomSource::large_feature_with_salt(seed, rx, rz, placement.salt);
+    let limit = placement.spacing - placement.separation;
+
+    // the linear spread of the attempt
+    rnd.next_i32_bound(limit);
+    rnd.next_i32_bound(limit);
+
+    if rnd.next_i32_bound(FORTRESS_WEIGHT + BASTION_WEIGHT) < FORTRESS_WEIGHT {
+        NetherStructure::Fortress
+    } else {
+        NetherStructure::Bastion
+    }
+}
+
+fn legacy_start(seed: i64, rx: i32, rz: i32) -> Option<NetherStart> {
+    let mut rnd = LegacyRandomSource::new((rx ^ (rz << 4)) as i64 ^ seed);
+    rnd.next_i32();
+
+    if rnd.next_i32_bound(LEGACY_CHANCE) != 0 {
+        return None;
+    }
+
+    let x = rx * LEGACY_SPACING + 4 + rnd.next_i32_bound(8);
+    let z = rz * LEGACY_SPACING + 4 + rnd.next_i32_bound(8);
+
+    Some(NetherStart {
+        structure: NetherStructure::Fortress,
+        chunk: ChunkPoint { x, z },
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::biome::NetherBiomeSource;
+    use crate::util::{ChunkPoint, Point3D, Version};
+
+    use super::{check_biome, nearest_fortress, region_start, starts, valid_starts, NetherStart, NetherStructure};
+
+    #[test]
+    fn test_region_start() {
+        let fortress = |x, z| NetherStart {
+            structure: NetherStructure::Fortress,
+            chunk: ChunkPoint { x, z },
+        };
+        let bastion = |x, z| NetherStart {
+            structure: NetherStructure::Bastion,
+            chunk: ChunkPoint { x, z },
+        };
+
+        // pinned from this implementation, the same as a transcription using `java.util.Random`
+        assert_eq!(region_start(123, -1, -1, Version::V1_20), Some(bastion(-25, -8)));
+        assert_eq!(region_start(123, -1, 0, Version::V1_20), Some(fortress(-10, 19)));
+        assert_eq!(region_start(123, 1, 1, Version::V1_18), Some(fortress(35, 34)));
+
+        // before 1.18 the pick continues the random of the region
+        assert_eq!(region_start(123, 0, -1, Version::V1_16), Some(bastion(5, -16)));
+        assert_eq!(region_start(123, 1, 1, Version::V1_16), Some(bastion(35, 34)));
+        assert_eq!(region_start(123, -1, 0, Version::V1_16), Some(bastion(-10, 19)));
+        assert_eq!(region_start(123, -1, -2, Version::V1_16), Some(fortress(-7, -36)));
+        assert_eq!(region_start(123, -1, -2, Version::V1_18), Some(bastion(-7, -36)));
+
+        assert_eq!(region_start(123, 0, 0, Version::V1_14), None);
+        assert_eq!(region_start(123, 1, -1, Version::V1_14), Some(fortress(24, -9)));
+        assert_eq!(region_start(123, 1, 1, Version::V1_12), Some(fortress(24, 22)));
+    }
+
+    #[test]
+    fn test_starts() {
+        let start = ChunkPoint { x: -27, z: -27 };
+        let end = ChunkPoint { x: 53, z: 53 };
+
+        let modern = starts(123, start, end, Version::V1_20);
+        assert_eq!(modern.len(), 9);
+        assert_eq!(
+            modern
+                .iter()
+                .filter(|s| s.structure == NetherStructure::Bastion)
+                .count(),
+            2
+        );
+
+        // only the region (1, -1) holds a fortress in 1.16
+        let nether_update = starts(123, start, end, Version::V1_16);
+        assert_eq!(
+            nether_update.iter().map(|s| s.chunk).collect::<Vec<_>>(),
+            modern.iter().map(|s| s.chunk).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            nether_update
+                .iter()
+                .filter(|s| s.structure == NetherStructure::Fortress)
+                .map(|s| s.chunk)
+                .collect::<Vec<_>>(),
+            vec![ChunkPoint { x: 47, z: -7 }]
+        );
+
+        let legacy = starts(
+            123,
+            ChunkPoint { x: -16, z: -16 },
+            ChunkPoint { x: 31, z: 31 },
+            Version::V1_14,
+        );
+        assert_eq!(legacy.len(), 2);
+    }
+
+    #[test]
+    fn test_nearest_fortress() {
+        let p = Point3D { x: 90, y: 64, z: 250 };
+        let nearest = nearest_fortress(123, p, Version::V1_20).unwrap();
+
+        // (6, 17) is closer than (-10, 19), the bastion at (-25, -8) is skipped
+        assert_eq!(nearest.chunk, ChunkPoint { x: 6, z: 17 });
+
+        // the region of the block holds a bastion, the closest fortress is one region west
+        let west = Point3D {
+            x: -400,
+            y: 64,
+            z: -130,
+        };
+        let nearest = nearest_fortress(123, west, Version::V1_20).unwrap();
+        assert_eq!(nearest.chunk, ChunkPoint { x: -48, z: -10 });
+
+        // (6, 17) holds a bastion in 1.16, the fortress at (-41, 30) is a little further away
+        let nearest = nearest_fortress(123, p, Version::V1_16).unwrap();
+        assert_eq!(nearest.chunk, ChunkPoint { x: 47, z: -7 });
+
+        let legacy = nearest_fortress(123, Point3D { x: 0, y: 64, z: 0 }, Version::V1_14).unwrap();
+        assert_eq!(legacy.structure, NetherStructure::Fortress);
+    }
+
+    #[test]
+    fn test_check_biome() {
+        let bastion = |x, z| NetherStart {
+            structure: NetherStructure::Bastion,
+            chunk: ChunkPoint { x, z },
+        };
+
+        // pinned from this implementation: the corner of the chunk lies in basalt deltas, its center
+        // in nether wastes
+        let start = bastion(11, -69);
+        let check = |version| check_biome(&NetherBiomeSource::new(123, version), start, version);
+
+        assert_eq!(
+            check(Version::V1_20),
+            Some(NetherStart {
+                structure: NetherStructure::Fortress,
+                ..start
+            })
+        );
+        assert_eq!(check(Version::V1_18), None);
+        assert_eq!(check(Version::V1_16), Some(start));
+
+        // deep inside basalt deltas
+        let source = NetherBiomeSource::new(123, Version::V1_16);
+        assert_eq!(check_biome(&source, bastion(-87, -26), Version::V1_16), None);
+
+        let start = ChunkPoint { x: -300, z: -300 };
+        let end = ChunkPoint { x: 300, z: 300 };
+        let all = starts(123, start, end, Version::V1_20);
+
+        // every bastion in basalt deltas becomes a fortress
+        let valid = valid_starts(123, start, end, Version::V1_20);
+        assert_eq!(valid.len(), all.len());
+        assert!(
+            valid
+                .iter()
+                .filter(|s| s.structure == NetherStructure::Fortress)
+                .count()
+                > all.iter().filter(|s| s.structure == NetherStructure::Fortress).count()
+        );
+
+        assert!(valid_starts(123, start, end, Version::V1_16).len() < all.len());
+    }
+}
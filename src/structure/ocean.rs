@@ -0,0 +1,133 @@
+//! Buried treasures, shipwrecks and ocean ruins.
+//!
+//! Buried treasures are not spread on a grid: every chunk rolls for one on its own. Shipwrecks and
+//! ocean ruins use regular region grids.
+
+use crate::util::{BlockPoint, ChunkPoint, Version};
+
+use super::StructureSet;
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum OceanStructure {
+    BuriedTreasure,
+    Shipwreck,
+    OceanRuin,
+}
+
+impl OceanStructure {
+    fn set(&self) -> StructureSet {
+        match self {
+            OceanStructure::BuriedTreasure => StructureSet::BuriedTreasure,
+            OceanStructure::Shipwreck => StructureSet::Shipwreck,
+            OceanStructure::OceanRuin => StructureSet::OceanRuin,
+        }
+    }
+}
+
+/// A chunk where an ocean structure is attempted.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct OceanCandidate {
+    pub structure: OceanStructure,
+    pub chunk: ChunkPoint,
+
+    /// The block reported by `/locate`. Buried treasures always sit at (9, 9) inside their chunk.
+    pub pos: BlockPoint,
+
+    /// Whether the biome at the attempt was checked. Buried treasures need a beach, shipwrecks and
+    /// ocean ruins an ocean, so unchecked candidates may not generate at all.
+    pub biome_checked: bool,
+}
+
+/// Returns every attempt of `structure` in the rectangular area outlined by `start` and `end`.
+pub fn candidates(
+    structure: OceanStructure,
+    seed: i64,
+    start: ChunkPoint,
+    end: ChunkPoint,
+    version: Version,
+) -> Vec<OceanCandidate> {
+    let placement = match structure.set().placement(version) {
+        Some(placement) => placement,
+        None => return Vec::new(),
+    };
+
+    placement
+        .structure_chunks(seed, start, end)
+        .into_iter()
+        .map(|chunk| OceanCandidate {
+            structure,
+            chunk,
+            pos: placement.locate_pos(chunk),
+            biome_checked: false,
+        })
+        .collect()
+}
+
+/// Returns the chunks holding a buried treasure candidate in the area outlined by `start` and `end`.
+pub fn buried_treasures(seed: i64, start: ChunkPoint, end: ChunkPoint, version: Version) -> Vec<OceanCandidate> {
+    candidates(OceanStructure::BuriedTreasure, seed, start, end, version)
+}
+
+/// Returns the shipwreck attempts in the area outlined by `start` and `end`.
+pub fn shipwrecks(seed: i64, start: ChunkPoint, end: ChunkPoint, version: Version) -> Vec<OceanCandidate> {
+    candidates(OceanStructure::Shipwreck, seed, start, end, version)
+}
+
+/// Returns the ocean ruin attempts in the area outlined by `start` and `end`.
+pub fn ocean_ruins(seed: i64, start: ChunkPoint, end: ChunkPoint, version: Version) -> Vec<OceanCandidate> {
+    candidates(OceanStructure::OceanRuin, seed, start, end, version)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::util::{ChunkPoint, Point3D, Version};
+
+    use super::{buried_treasures, ocean_ruins, shipwrecks, OceanStructure};
+
+    #[test]
+    fn test_buried_treasures() {
+        let start = ChunkPoint { x: -100, z: -100 };
+        let end = ChunkPoint { x: 99, z: 99 };
+
+        // pinned from this implementation
+        let treasures = buried_treasures(123, start, end, Version::V1_20);
+        assert_eq!(treasures.len(), 444);
+        assert_eq!(treasures[0].chunk, ChunkPoint { x: -100, z: -21 });
+        assert_eq!(
+            treasures[0].pos,
+            Point3D {
+                x: -1591,
+                y: 0,
+                z: -327
+            }
+        );
+
+        for t in &treasures {
+            assert_eq!(t.structure, OceanStructure::BuriedTreasure);
+            assert_eq!((t.pos.x.rem_euclid(16), t.pos.z.rem_euclid(16)), (9, 9));
+            assert!(!t.biome_checked);
+        }
+
+        // the check is the same since 1.13
+        assert_eq!(buried_treasures(123, start, end, Version::V1_13), treasures);
+    }
+
+    #[test]
+    fn test_grid_candidates() {
+        let start = ChunkPoint { x: 0, z: 0 };
+        let end = ChunkPoint { x: 239, z: 239 };
+
+        // one attempt per region
+        let wrecks = shipwrecks(123, start, end, Version::V1_20);
+        assert_eq!(wrecks.len(), 100);
+        assert!(wrecks.iter().all(|w| w.pos == w.chunk.into() && !w.biome_checked));
+
+        let ruins = ocean_ruins(123, start, end, Version::V1_20);
+        assert_eq!(ruins.len(), 144);
+        assert!(ruins.iter().all(|r| r.structure == OceanStructure::OceanRuin));
+
+        // 16 chunk regions before 1.16
+        assert_eq!(shipwrecks(123, start, end, Version::V1_14).len(), 225);
+        assert_eq!(ocean_ruins(123, start, end, Version::V1_14).len(), 225);
+    }
+}
@@ -0,0 +1,178 @@
+use crate::rand::source::{LegacyRandomSource, RandomSource};
+use crate::util::{BlockPoint, ChunkPoint, Point3D};
+
+/// How the attempt of a region is spread out inside of it.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum SpreadType {
+    Linear,
+
+    /// Biased towards the center of the region.
+    Triangular,
+}
+
+impl SpreadType {
+    fn evaluate(&self, rnd: &mut LegacyRandomSource, limit: i32) -> i32 {
+        match self {
+            SpreadType::Linear => rnd.next_i32_bound(limit),
+            SpreadType::Triangular => (rnd.next_i32_bound(limit) + rnd.next_i32_bound(limit)) / 2,
+        }
+    }
+}
+
+/// The extra check used to thin out the attempts of structures with a `frequency` under `1.0`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum FrequencyReduction {
+    Default,
+
+    /// Used by pillager outposts.
+    LegacyType1,
+
+    /// Used by buried treasures.
+    LegacyType2,
+
+    /// Used by mineshafts.
+    LegacyType3,
+}
+
+impl FrequencyReduction {
+    fn should_generate(&self, seed: i64, salt: i32, x: i32, z: i32, frequency: f32) -> bool {
+        match self {
+            FrequencyReduction::Default => {
+                // NOTE: the argument order mirrors the vanilla source, salt and coordinates are
+                // shifted on purpose
+                let mut rnd = LegacyRandomSource::large_feature_with_salt(seed, salt, x, z);
+                rnd.next_f32() < frequency
+            }
+            FrequencyReduction::LegacyType1 => {
+                let (i, j) = (x >> 4, z >> 4);
+
+                let mut rnd = LegacyRandomSource::new((i ^ (j << 4)) as i64 ^ seed);
+                rnd.next_i32();
+
+                rnd.next_i32_bound((1f32 / frequency) as i32) == 0
+            }
+            FrequencyReduction::LegacyType2 => {
+                let mut rnd = LegacyRandomSource::large_feature_with_salt(seed, x, z, 10387320);
+                rnd.next_f32() < frequency
+            }
+            FrequencyReduction::LegacyType3 => {
+                let mut rnd = LegacyRandomSource::large_feature(seed, x, z);
+                rnd.next_f64() < frequency as f64
+            }
+        }
+    }
+}
+
+/// Feature parity with Minecraft's `RandomSpreadStructurePlacement`.
+///
+/// The world is split into square regions of `spacing` chunks, each of which holds exactly one
+/// structure attempt. The attempt is placed at least `separation` chunks away from the next region.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct RandomSpreadPlacement {
+    pub spacing: i32,
+    pub separation: i32,
+    pub salt: i32,
+    pub spread: SpreadType,
+    pub frequency: f32,
+    pub frequency_reduction: FrequencyReduction,
+
+    /// Offset from the corner of the structure chunk reported by `/locate`.
+    pub locate_offset: BlockPoint,
+}
+
+impl RandomSpreadPlacement {
+    /// A linear placement which places a structure in every region.
+    pub fn new(spacing: i32, separation: i32, salt: i32) -> Self {
+        assert!(separation < spacing, "spacing has to be larger than separation");
+
+        RandomSpreadPlacement {
+            spacing,
+            separation,
+            salt,
+            spread: SpreadType::Linear,
+            frequency: 1f32,
+            frequency_reduction: FrequencyReduction::Default,
+            locate_offset: Point3D { x: 0, y: 0, z: 0 },
+        }
+    }
+
+    pub fn with_spread(self, spread: SpreadType) -> Self {
+        RandomSpreadPlacement { spread, ..self }
+    }
+
+    pub fn with_frequency(self, frequency: f32, frequency_reduction: FrequencyReduction) -> Self {
+        RandomSpreadPlacement {
+            frequency,
+            frequency_reduction,
+            ..self
+        }
+    }
+
+    pub fn with_locate_offset(self, locate_offset: BlockPoint) -> Self {
+        RandomSpreadPlacement { locate_offset, ..self }
+    }
+
+    /// Returns the region holding the chunk `c`.
+    pub fn region(&self, c: ChunkPoint) -> (i32, i32) {
+        (c.x.div_euclid(self.spacing), c.z.div_euclid(self.spacing))
+    }
+
+    /// Returns the chunk holding the structure attempt of region (`rx`, `rz`) for a given `seed`.
+    pub fn potential_chunk(&self, seed: i64, rx: i32, rz: i32) -> ChunkPoint {
+        let mut rnd = LegacyRandomSource::large_feature_with_salt(seed, rx, rz, self.salt);
+        let limit = self.spacing - self.separation;
+
+        let x = self.spread.evaluate(&mut rnd, limit);
+        let z = self.spread.evaluate(&mut rnd, limit);
+
+        ChunkPoint {
+            x: rx * self.spacing + x,
+            z: rz * self.spacing + z,
+        }
+    }
+
+    /// Checks if `c` holds the structure attempt of its region.
+    pub fn is_placement_chunk(&self, seed: i64, c: ChunkPoint) -> bool {
+        let (rx, rz) = self.region(c);
+
+        self.potential_chunk(seed, rx, rz) == c
+    }
+
+    /// Checks if a structure is attempted in `c`, including the frequency check but not the biome
+    /// check or the interactions with other structures.
+    pub fn is_structure_chunk(&self, seed: i64, c: ChunkPoint) -> bool {
+        self.is_placement_chunk(seed, c) && self.passes_frequency(seed, c)
+    }
+
+    fn passes_frequency(&self, seed: i64, c: ChunkPoint) -> bool {
+        self.frequency >= 1f32 || self.frequency_reduction.should_generate(seed, self.salt, c.x, c.z, self.frequency)
+    }
+
+    /// Returns every chunk in the rectangular area outlined by `start` and `end` which passes
+    /// `is_structure_chunk`.
+    pub fn structure_chunks(&self, seed: i64, start: ChunkPoint, end: ChunkPoint) -> Vec<ChunkPoint> {
+        assert!(start.x <= end.x);
+        assert!(start.z <= end.z);
+
+        let (rx0, rz0) = self.region(start);
+        let (rx1, rz1) = self.region(end);
+
+        (rx0..=rx1)
+            .flat_map(|rx| (rz0..=rz1).map(move |rz| (rx, rz)))
+            .map(|(rx, rz)| self.potential_chunk(seed, rx, rz))
+            .filter(|c| start.x <= c.x && c.x <= end.x && start.z <= c.z && c.z <= end.z)
+            .filter(|c| self.passes_frequency(seed, *c))
+            .collect()
+    }
+
+    /// Returns the block reported by `/locate` for a structure in chunk `c`.
+    pub fn locate_pos(&self, c: ChunkPoint) -> BlockPoint {
+        let p: BlockPoint = c.into();
+
+        Point3D {
+            x: p.x + self.locate_offset.x,
+            y: p.y + self.locate_offset.y,
+            z: p.z + self.locate_offset.z,
+        }
+    }
+}
@@ -0,0 +1,268 @@
+//! Strongholds are not placed on a region grid but on concentric rings around the origin.
+//!
+//! Every stronghold is moved to a preferred biome up to `SNAP_RADIUS` blocks away from its ring
+//! position. Biomes are not evaluated here, so the predicted chunks are approximate.
+
+use crate::rand::source::{LegacyRandomSource, RandomSource};
+use crate::util::{BlockPoint, ChunkPoint, Version};
+
+/// The number of strongholds in a world.
+pub const COUNT: usize = 128;
+
+/// The width of a ring, in chunks.
+pub const DISTANCE: i32 = 32;
+
+/// The number of strongholds in the innermost ring.
+pub const SPREAD: usize = 3;
+
+/// The furthest a stronghold is moved to reach a preferred biome, in blocks.
+pub const SNAP_RADIUS: i32 = 112;
+
+/// The precision of a yaw read off the debug screen, in degrees.
+pub const YAW_PRECISION: f64 = 0.1;
+
+/// A stronghold at its ring position, before it is moved to a preferred biome.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Stronghold {
+    pub chunk: ChunkPoint,
+
+    /// The ring holding the stronghold, starting at `0` for the innermost one.
+    pub ring: u32,
+
+    /// Whether the ring position depends on the biomes around earlier strongholds. Prior to 1.19.3
+    /// the biome search shares its random with the ring placement, so every position after the
+    /// first one is only exact if no earlier search drew from it. This says nothing about the
+    /// stronghold itself, which is moved to a preferred biome in every version.
+    pub depends_on_earlier_biomes: bool,
+}
+
+/// Returns the ring positions of all `COUNT` strongholds for a `seed`, innermost first.
+pub fn strongholds(seed: i64, version: Version) -> Vec<Stronghold> {
+    let forks = version >= Version::V1_19_3;
+    let mut rnd = LegacyRandomSource::new(seed);
+
+    let mut angle = rnd.next_f64() * std::f64::consts::PI * 2.0;
+    let mut spread = SPREAD;
+    let mut in_ring = 0;
+    let mut ring = 0;
+
+    let mut res = Vec::with_capacity(COUNT);
+
+    for n in 0..COUNT {
+        let dist = (4 * DISTANCE + DISTANCE * ring * 6) as f64 + (rnd.next_f64() - 0.5) * (DISTANCE as f64 * 2.5);
+
+        res.push(Stronghold {
+            chunk: ChunkPoint {
+                x: java_round(angle.cos() * dist),
+                z: java_round(angle.sin() * dist),
+            },
+            ring: ring as u32,
+            depends_on_earlier_biomes: !forks && n > 0,
+        });
+
+        // the biome search gets its own random
+        if forks {
+            rnd.fork();
+        }
+
+        angle += std::f64::consts::PI * 2.0 / spread as f64;
+        in_ring += 1;
+
+        if in_ring == spread {
+            in_ring = 0;
+            ring += 1;
+            spread += 2 * spread / (ring as usize + 1);
+            spread = spread.min(COUNT - n);
+            angle += rnd.next_f64() * std::f64::consts::PI * 2.0;
+        }
+    }
+
+    res
+}
+
+/// `Math.round`
+fn java_round(x: f64) -> i32 {
+    (x + 0.5).floor() as i32
+}
+
+/// An eye of ender thrown from `pos`, flying towards `yaw` as shown on the debug screen.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct EyeThrow {
+    pub pos: BlockPoint,
+    pub yaw: f64,
+}
+
+impl EyeThrow {
+    pub fn new(pos: BlockPoint, yaw: f64) -> Self {
+        EyeThrow { pos, yaw }
+    }
+
+    fn origin(&self) -> (f64, f64) {
+        (self.pos.x as f64 + 0.5, self.pos.z as f64 + 0.5)
+    }
+
+    /// The unit vector the eye flies along. A yaw of `0` faces south, `90` faces west.
+    fn direction(&self) -> (f64, f64) {
+        let yaw = self.yaw.to_radians();
+
+        (-yaw.sin(), yaw.cos())
+    }
+}
+
+/// The stronghold estimated from a set of eye throws.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Triangulation {
+    pub chunk: ChunkPoint,
+
+    /// How far off the estimate likely is, in blocks.
+    pub error: f64,
+}
+
+/// Estimates the stronghold chunk the eyes of `throws` fly towards, from two or more throws.
+///
+/// The error is the larger of the spread caused by yaws only being precise to `YAW_PRECISION` and
+/// the disagreement between the throws. Returns `None` if the throws are parallel or point away
+/// from each other.
+pub fn triangulate(throws: &[EyeThrow]) -> Option<Triangulation> {
+    assert!(throws.len() >= 2, "triangulation needs at least two throws");
+
+    let lines: Vec<((f64, f64), (f64, f64))> = throws
+        .iter()
+        .map(|t| {
+            let (dx, dz) = t.direction();
+            (t.origin(), (-dz, dx))
+        })
+        .collect();
+
+    // far throws are less precise, so reweight by the distance to the previous estimate
+    let mut weights = vec![1f64; lines.len()];
+    let mut estimate = (0f64, 0f64);
+    let mut normal_matrix = (0f64, 0f64, 0f64);
+
+    for _ in 0..3 {
+        let (mut a, mut b, mut c) = (0f64, 0f64, 0f64);
+        let (mut u, mut v) = (0f64, 0f64);
+
+        for (((ox, oz), (nx, nz)), w) in lines.iter().zip(&weights) {
+            let d = nx * ox + nz * oz;
+
+            a += w * nx * nx;
+            b += w * nx * nz;
+            c += w * nz * nz;
+            u += w * nx * d;
+            v += w * nz * d;
+        }
+
+        let det = a * c - b * b;
+        if det.abs() < 1e-12 * (a * c).max(f64::MIN_POSITIVE) {
+            return None;
+        }
+
+        estimate = ((c * u - b * v) / det, (a * v - b * u) / det);
+        normal_matrix = (a, b, c);
+
+        for (((ox, oz), _), w) in lines.iter().zip(weights.iter_mut()) {
+            let sigma = (estimate.0 - ox).hypot(estimate.1 - oz) * YAW_PRECISION.to_radians().tan();
+            *w = 1.0 / sigma.max(1e-6).powi(2);
+        }
+    }
+
+    // the eyes have to fly towards the estimate
+    let ahead = throws.iter().all(|t| {
+        let ((ox, oz), (dx, dz)) = (t.origin(), t.direction());
+        (estimate.0 - ox) * dx + (estimate.1 - oz) * dz > 0.0
+    });
+
+    if !ahead {
+        return None;
+    }
+
+    // the largest axis of the covariance, the inverse of the last normal matrix
+    let (a, b, c) = normal_matrix;
+    let det = a * c - b * b;
+    let (ia, ib, ic) = (c / det, -b / det, a / det);
+    let spread = ((ia + ic) / 2.0 + (((ia - ic) / 2.0).powi(2) + ib * ib).sqrt()).sqrt();
+
+    let residual = (lines
+        .iter()
+        .map(|((ox, oz), (nx, nz))| (nx * (estimate.0 - ox) + nz * (estimate.1 - oz)).powi(2))
+        .sum::<f64>()
+        / lines.len() as f64)
+        .sqrt();
+
+    // eyes fly towards the corner of the stronghold chunk
+    Some(Triangulation {
+        chunk: ChunkPoint {
+            x: (estimate.0 / 16.0).round() as i32,
+            z: (estimate.1 / 16.0).round() as i32,
+        },
+        error: spread.max(residual),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::util::{ChunkPoint, Point3D, Version};
+
+    use super::{strongholds, triangulate, EyeThrow, COUNT};
+
+    #[test]
+    fn test_strongholds() {
+        // ring positions pinned from this implementation, the placement ignores biomes since 1.19.3
+        let modern = strongholds(123, Version::V1_20);
+        assert_eq!(modern.len(), COUNT);
+        assert_eq!(modern[0].chunk, ChunkPoint { x: -28, z: -165 });
+        assert_eq!(modern[1].chunk, ChunkPoint { x: 128, z: 48 });
+        assert_eq!(modern[3].chunk, ChunkPoint { x: 96, z: -330 });
+        assert_eq!(modern[9].chunk, ChunkPoint { x: 457, z: 159 });
+        assert_eq!(modern[127].chunk, ChunkPoint { x: -261, z: -1472 });
+        assert_eq!((modern[2].ring, modern[3].ring, modern[127].ring), (0, 1, 7));
+        assert!(modern.iter().all(|s| !s.depends_on_earlier_biomes));
+
+        // computed as if no biome search drew from the random, the game's positions after the first
+        // one depend on the biomes of the world
+        let legacy = strongholds(123, Version::V1_16);
+        assert_eq!(legacy[0].chunk, modern[0].chunk);
+        assert_eq!(legacy[1].chunk, ChunkPoint { x: 102, z: 38 });
+        assert_eq!(legacy[127].chunk, ChunkPoint { x: 1301, z: 604 });
+        assert!(!legacy[0].depends_on_earlier_biomes && legacy[1].depends_on_earlier_biomes);
+
+        // the rings hold 3, 6, 10, 15, 21, 28, 36 and the remaining 9 strongholds
+        let per_ring: Vec<usize> = (0..8).map(|r| modern.iter().filter(|s| s.ring == r).count()).collect();
+        assert_eq!(per_ring, vec![3, 6, 10, 15, 21, 28, 36, 9]);
+    }
+
+    fn throw_towards(x: i32, z: i32, target: (f64, f64)) -> EyeThrow {
+        let dx = target.0 - (x as f64 + 0.5);
+        let dz = target.1 - (z as f64 + 0.5);
+        let yaw = (-dx).atan2(dz).to_degrees();
+
+        // the debug screen rounds to a tenth of a degree
+        EyeThrow::new(Point3D { x, y: 70, z }, (yaw * 10.0).round() / 10.0)
+    }
+
+    #[test]
+    fn test_triangulate() {
+        let target = (1600.0, -800.0);
+
+        let two = [throw_towards(0, 0, target), throw_towards(300, 200, target)];
+        let res = triangulate(&two).unwrap();
+        assert_eq!(res.chunk, ChunkPoint { x: 100, z: -50 });
+        assert!(res.error > 0.0 && res.error < 48.0);
+
+        let three = [two[0], two[1], throw_towards(1000, -900, target)];
+        let res3 = triangulate(&three).unwrap();
+        assert_eq!(res3.chunk, ChunkPoint { x: 100, z: -50 });
+        assert!(res3.error < res.error);
+
+        // parallel and diverging throws
+        let parallel = [
+            EyeThrow::new(Point3D { x: 0, y: 70, z: 0 }, 45.0),
+            EyeThrow::new(Point3D { x: 10, y: 70, z: 0 }, 45.0),
+        ];
+        assert!(triangulate(&parallel).is_none());
+
+        let away = [throw_towards(0, 0, target), throw_towards(300, 200, (-1600.0, 800.0))];
+        assert!(triangulate(&away).is_none());
+    }
+}
@@ -0,0 +1,79 @@
+use std::fmt::Display;
+
+use super::{BlockPoint, Point3D};
+
+/// An axis aligned box of blocks. Both corners are included in the box.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct BoundingBox {
+    pub min: BlockPoint,
+    pub max: BlockPoint,
+}
+
+impl BoundingBox {
+    /// Creates the box spanning the two corners `p1` and `p2`, in any order.
+    pub fn new(p1: BlockPoint, p2: BlockPoint) -> Self {
+        BoundingBox {
+            min: Point3D {
+                x: p1.x.min(p2.x),
+                y: p1.y.min(p2.y),
+                z: p1.z.min(p2.z),
+            },
+            max: Point3D {
+                x: p1.x.max(p2.x),
+                y: p1.y.max(p2.y),
+                z: p1.z.max(p2.z),
+            },
+        }
+    }
+
+    /// The number of blocks along the `x` axis.
+    pub fn width(&self) -> i64 {
+        (self.max.x - self.min.x) as i64 + 1
+    }
+
+    /// The number of blocks along the `y` axis.
+    pub fn height(&self) -> i64 {
+        (self.max.y - self.min.y) as i64 + 1
+    }
+
+    /// The number of blocks along the `z` axis.
+    pub fn depth(&self) -> i64 {
+        (self.max.z - self.min.z) as i64 + 1
+    }
+
+    /// The number of blocks in the box.
+    pub fn volume(&self) -> i64 {
+        self.width() * self.height() * self.depth()
+    }
+
+    pub fn contains(&self, p: &BlockPoint) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x)
+            && (self.min.y..=self.max.y).contains(&p.y)
+            && (self.min.z..=self.max.z).contains(&p.z)
+    }
+}
+
+impl Display for BoundingBox {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Box(from: {}, to: {})", self.min, self.max)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::util::Point3D;
+
+    use super::BoundingBox;
+
+    #[test]
+    fn test_bounding_box() {
+        let b = BoundingBox::new(Point3D { x: 3, y: 70, z: -2 }, Point3D { x: -1, y: 64, z: 5 });
+
+        assert_eq!(b.min, Point3D { x: -1, y: 64, z: -2 });
+        assert_eq!(b.max, Point3D { x: 3, y: 70, z: 5 });
+        assert_eq!((b.width(), b.height(), b.depth()), (5, 7, 8));
+        assert_eq!(b.volume(), 280);
+        assert!(b.contains(&Point3D { x: 0, y: 64, z: 5 }));
+        assert!(!b.contains(&Point3D { x: 0, y: 63, z: 5 }));
+    }
+}
@@ -1,8 +1,18 @@
 pub const OVERWORLD_MIN: i32 = -64;
 pub const OVERWORLD_MAX: i32 = 320;
 
+/// The height range of the overworld before 1.18.
+pub const LEGACY_OVERWORLD_MIN: i32 = 0;
+pub const LEGACY_OVERWORLD_MAX: i32 = 256;
+
 pub const NETHER_MIN: i32 = 0;
 pub const NETHER_MAX: i32 = 256;
 
 pub const END_MIN: i32 = 0;
 pub const END_MAX: i32 = 256;
+
+/// The most blocks a single `fill` or `clone` command may change.
+pub const MAX_FILL_VOLUME: i64 = 32768;
+
+/// The most characters a command block accepts.
+pub const MAX_COMMAND_LENGTH: usize = 32500;
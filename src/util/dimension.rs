@@ -0,0 +1,57 @@
+use std::fmt::Display;
+
+use crate::util::consts::{
+    END_MAX, END_MIN, LEGACY_OVERWORLD_MAX, LEGACY_OVERWORLD_MIN, NETHER_MAX, NETHER_MIN, OVERWORLD_MAX, OVERWORLD_MIN,
+};
+use crate::util::Version;
+
+/// One of the vanilla dimensions, with the range of heights blocks can be placed at.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Dimension {
+    Overworld,
+    Nether,
+    End,
+}
+
+impl Dimension {
+    /// The lowest height blocks can be placed at in `version`.
+    pub fn min_y(&self, version: Version) -> i32 {
+        match self {
+            Dimension::Overworld if version < Version::V1_18 => LEGACY_OVERWORLD_MIN,
+            Dimension::Overworld => OVERWORLD_MIN,
+            Dimension::Nether => NETHER_MIN,
+            Dimension::End => END_MIN,
+        }
+    }
+
+    /// The height limit in `version`, blocks can be placed up to `max_y(version) - 1`. The
+    /// overworld grew from `0..256` to `-64..320` in 1.18.
+    pub fn max_y(&self, version: Version) -> i32 {
+        match self {
+            Dimension::Overworld if version < Version::V1_18 => LEGACY_OVERWORLD_MAX,
+            Dimension::Overworld => OVERWORLD_MAX,
+            Dimension::Nether => NETHER_MAX,
+            Dimension::End => END_MAX,
+        }
+    }
+
+    /// The number of blocks in a column, from the bottom to the top of the world.
+    pub fn height(&self, version: Version) -> i32 {
+        self.max_y(version) - self.min_y(version)
+    }
+
+    /// The id used in commands, `minecraft:the_nether` for example.
+    pub fn id(&self) -> &'static str {
+        match self {
+            Dimension::Overworld => "minecraft:overworld",
+            Dimension::Nether => "minecraft:the_nether",
+            Dimension::End => "minecraft:the_end",
+        }
+    }
+}
+
+impl Display for Dimension {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.id())
+    }
+}
@@ -1,8 +1,14 @@
 pub mod consts;
+pub mod dimension;
 pub mod point;
+pub mod version;
+pub mod bounding_box;
 
 /// Re-exports
 pub use point::Point3D;
 pub use point::FlatPoint;
 pub use point::BlockPoint;
 pub use point::ChunkPoint;
+pub use version::Version;
+pub use dimension::Dimension;
+pub use bounding_box::BoundingBox;
@@ -2,7 +2,7 @@ use std::fmt::{Debug, Display};
 
 use num_traits::Num;
 
-#[derive(Copy, Clone, PartialEq)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
 pub struct Point3D<T: Num> {
     pub x: T,
     pub y: T,
@@ -49,7 +49,7 @@ impl From<ChunkPoint> for BlockPoint {
 
 /// `i32`s are required to access any chunk coordinate in the world. The sidelength of the
 /// Minecraft world spans `3.75` million chunks.
-#[derive(Copy, Clone, PartialEq)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
 pub struct ChunkPoint {
     pub x: i32,
     pub z: i32
@@ -68,11 +68,30 @@ impl Display for ChunkPoint {
 }
 
 impl From<BlockPoint> for ChunkPoint {
-    /// Convert from a `BlockPoint` to a `ChunkPoint`.
+    /// Convert from a `BlockPoint` to the `ChunkPoint` holding it. Rounds towards negative
+    /// infinity like the game, block `-1` is in chunk `-1`.
     fn from(value: BlockPoint) -> Self {
         Self {
-            x: value.x / 16,
-            z: value.z / 16,
+            x: value.x >> 4,
+            z: value.z >> 4,
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{BlockPoint, ChunkPoint, Point3D};
+
+    #[test]
+    fn test_chunk_of_block() {
+        let chunk = |x, z| ChunkPoint::from(Point3D { x, y: 64, z });
+
+        assert_eq!(chunk(0, 15), ChunkPoint { x: 0, z: 0 });
+        assert_eq!(chunk(16, 31), ChunkPoint { x: 1, z: 1 });
+        assert_eq!(chunk(-1, -16), ChunkPoint { x: -1, z: -1 });
+        assert_eq!(chunk(-17, -15), ChunkPoint { x: -2, z: -1 });
+
+        let back = BlockPoint::from(chunk(-17, 40));
+        assert_eq!(back, Point3D { x: -32, y: 0, z: 32 });
+    }
+}
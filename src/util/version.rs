@@ -0,0 +1,36 @@
+use std::fmt::Display;
+
+/// A Minecraft: Java Edition release, `1.minor.patch`.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Version {
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl Version {
+    pub const V1_12: Version = Version::new(12, 0);
+    pub const V1_13: Version = Version::new(13, 0);
+    pub const V1_14: Version = Version::new(14, 0);
+    pub const V1_16: Version = Version::new(16, 0);
+    pub const V1_18: Version = Version::new(18, 0);
+    pub const V1_18_2: Version = Version::new(18, 2);
+    pub const V1_19: Version = Version::new(19, 0);
+    pub const V1_19_3: Version = Version::new(19, 3);
+    pub const V1_20: Version = Version::new(20, 0);
+    pub const V1_20_5: Version = Version::new(20, 5);
+    pub const V1_21: Version = Version::new(21, 0);
+
+    pub const fn new(minor: u32, patch: u32) -> Self {
+        Version { minor, patch }
+    }
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.patch == 0 {
+            write!(f, "1.{}", self.minor)
+        } else {
+            write!(f, "1.{}.{}", self.minor, self.patch)
+        }
+    }
+}
@@ -6,7 +6,6 @@ use ndarray::Array2;
 use regex::Regex;
 use std::fs;
 use std::io;
-use std::io::ErrorKind;
 use std::io::Result;
 use std::path::PathBuf;
 
@@ -31,7 +30,7 @@ fn load_file(name: &str) -> io::Result<SlimeMat> {
 
     let meta_line = lines
         .next()
-        .ok_or(io::Error::new(ErrorKind::Other, "missing meta line"))?;
+        .ok_or(io::Error::other("missing meta line"))?;
     let meta_regex = Regex::new(r"x: (-?\d+), z: (-?\d+), s: (-?\d+)").unwrap();
     let meta_fields = meta_regex.captures(meta_line).unwrap();
 